@@ -0,0 +1,218 @@
+//! Checks the physics extras of the project's glTF files without opening a window
+//! - Usage: `cargo run --no-default-features --bin lint_extras -- [--deny-warnings] [files...]`, defaults to every `.gltf`/`.glb` file in `assets`
+//! - Errors are extras the game can't use as authored, warnings are extras that work but look wrong
+//! - Exits with a non-zero code on errors (and on warnings with `--deny-warnings`) so asset commits can be gated
+
+use std::{collections::HashSet, fs, path::{Path, PathBuf}, process::ExitCode};
+
+use rusty_physics::extras::{BCollider, BMeshExtras, ExtrasError, ExtrasIssue, MeshBounds, ShapeContext};
+
+/// Relative difference between the collider and mesh sizes above which a mismatch is reported
+const SIZE_TOLERANCE: f32 = 0.1;
+
+/// Problems found in every linted file
+#[derive(Default)]
+struct Report {
+    errors: Vec<ExtrasError>,
+    warnings: Vec<ExtrasError>,
+}
+
+/// A mesh primitive along with where it sits in its glTF file
+struct MeshNode<'a> {
+    scene: String,
+    node: String,
+    mesh: gltf::Mesh<'a>,
+    /// Scale accumulated from the scene root down to the node, like `GltfPhysicsQueries::scene_scale`
+    scale: [f32; 3],
+}
+
+impl MeshNode<'_> {
+    fn error(&self, report: &mut Report, issue: &ExtrasIssue) {
+        report.errors.push(self.describe(issue));
+    }
+
+    fn warning(&self, report: &mut Report, field: Option<&str>, message: String) {
+        report.warnings.push(ExtrasError {
+            scene: self.scene.clone(),
+            node: self.node.clone(),
+            mesh: self.mesh_name(),
+            field: field.map(str::to_string),
+            message,
+        });
+    }
+
+    fn describe(&self, issue: &ExtrasIssue) -> ExtrasError {
+        ExtrasError::new(self.scene.clone(), self.node.clone(), self.mesh_name(), issue)
+    }
+
+    fn mesh_name(&self) -> String {
+        self.mesh.name().unwrap_or_default().to_string()
+    }
+}
+
+fn main() -> ExitCode {
+    let mut deny_warnings = false;
+    let mut files = Vec::new();
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--deny-warnings" => deny_warnings = true,
+            _ => files.push(PathBuf::from(arg)),
+        }
+    }
+    if files.is_empty() {
+        files = asset_files(Path::new("assets"));
+    }
+
+    let mut report = Report::default();
+    for file in &files {
+        if let Err(err) = lint_file(file, &mut report) {
+            eprintln!("error: could not read {}: {}", file.display(), err);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    for warning in &report.warnings {
+        println!("warning: {}", warning);
+    }
+    for error in &report.errors {
+        println!("error: {}", error);
+    }
+    println!(
+        "{} file(s) linted: {} error(s), {} warning(s)",
+        files.len(),
+        report.errors.len(),
+        report.warnings.len(),
+    );
+
+    if !report.errors.is_empty() || (deny_warnings && !report.warnings.is_empty()) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Every `.gltf`/`.glb` file directly inside `dir`, sorted so the output is stable
+fn asset_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "gltf" || extension == "glb"))
+        .collect();
+    files.sort();
+    files
+}
+
+/// Checks every scene of a file, only the JSON is read so external buffers aren't needed
+fn lint_file(path: &Path, report: &mut Report) -> Result<(), gltf::Error> {
+    let gltf = gltf::Gltf::open(path)?;
+    let file = path.file_name().map_or_else(|| path.display().to_string(), |name| name.to_string_lossy().to_string());
+    for scene in gltf.scenes() {
+        let scene_name = format!("{}#{}", file, scene.name().map_or_else(|| format!("Scene{}", scene.index()), str::to_string));
+        let mut nodes = Vec::new();
+        for root in scene.nodes() {
+            collect_nodes(&scene_name, root, [1.0; 3], &mut nodes);
+        }
+
+        // Like the game, joints can only target nodes whose extras parse, the other nodes never become bodies
+        let bodies: HashSet<&str> = nodes
+            .iter()
+            .filter(|node| node.mesh.extras().as_ref().is_some_and(|extras| BMeshExtras::parse(extras.get()).is_ok()))
+            .map(|node| node.node.as_str())
+            .collect();
+        let mut has_physics = false;
+        for node in &nodes {
+            let Some(extras) = node.mesh.extras() else {
+                continue;
+            };
+            has_physics = true;
+            lint_mesh(node, extras.get(), &bodies, report);
+        }
+        if !has_physics {
+            report.warnings.push(ExtrasError {
+                scene: scene_name,
+                node: String::new(),
+                mesh: String::new(),
+                field: None,
+                message: "scene has no mesh with physics extras".to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Walks the node hierarchy the same way the scene is spawned, keeping every node with a mesh
+fn collect_nodes<'a>(scene: &str, node: gltf::Node<'a>, parent_scale: [f32; 3], nodes: &mut Vec<MeshNode<'a>>) {
+    let (_, _, scale) = node.transform().decomposed();
+    let scale = [0, 1, 2].map(|axis| parent_scale[axis] * scale[axis]);
+    let name = node.name().map_or_else(|| format!("Node{}", node.index()), str::to_string);
+    if let Some(mesh) = node.mesh() {
+        nodes.push(MeshNode { scene: scene.to_string(), node: name, mesh, scale });
+    }
+    for child in node.children() {
+        collect_nodes(scene, child, scale, nodes);
+    }
+}
+
+/// Runs the extras of a mesh through the same parsing and shape building as `process_gltf_descendants`
+fn lint_mesh(node: &MeshNode, extras: &str, bodies: &HashSet<&str>, report: &mut Report) {
+    let data = match BMeshExtras::parse(extras) {
+        Ok(data) => data,
+        Err(issue) => {
+            node.error(report, &issue);
+            return;
+        }
+    };
+
+    for joint in &data.joints {
+        if !bodies.contains(joint.target.as_str()) {
+            node.error(report, &ExtrasIssue::InvalidJoint { target: joint.target.clone(), message: "targets an unknown node".to_string() });
+        }
+    }
+
+    if data.collider.is_mesh_based() {
+        return;
+    }
+
+    // Missing sizes only work because the game derives them from the mesh bounds
+    let missing = match data.collider {
+        BCollider::Cuboid if data.cube_size.is_none() => Some("cube_size"),
+        BCollider::Sphere | BCollider::Cylinder | BCollider::Capsule | BCollider::Cone if data.radius.is_none() => Some("radius"),
+        BCollider::Cylinder | BCollider::Capsule | BCollider::Cone if data.height.is_none() => Some("height"),
+        _ => None,
+    };
+    if let Some(field) = missing {
+        node.warning(report, Some(field), format!("{:?} collider has no {}, it is derived from the mesh bounds", data.collider, field));
+    }
+
+    for primitive in node.mesh.primitives() {
+        let bounds = primitive.bounding_box();
+        let context = ShapeContext {
+            scale: node.scale,
+            bounds: Some(MeshBounds::from_min_max(bounds.min, bounds.max)),
+        };
+        let shape = match data.shape(context) {
+            Ok(shape) => shape,
+            Err(issue) => {
+                node.error(report, &issue);
+                continue;
+            }
+        };
+
+        // Both sizes are compared in the node's local space, then reported in world space like the extras
+        let collider_size = shape.size();
+        let mesh_size = [0, 1, 2].map(|axis| bounds.max[axis] - bounds.min[axis]);
+        let mismatch = (0..3).any(|axis| (collider_size[axis] - mesh_size[axis]).abs() > mesh_size[axis].abs() * SIZE_TOLERANCE);
+        if mismatch {
+            let world = |size: [f32; 3]| [0, 1, 2].map(|axis| size[axis] * node.scale[axis]);
+            let message = format!(
+                "{:?} collider is {:?} but the mesh is {:?} (world space)",
+                data.collider,
+                world(collider_size),
+                world(mesh_size),
+            );
+            node.warning(report, None, message);
+        }
+    }
+}
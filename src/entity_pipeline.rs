@@ -1,140 +1,588 @@
+use std::{collections::{HashMap, HashSet}, time::{Duration, Instant}};
+
 use avian3d::prelude::*;
-use bevy::{ gltf::GltfMeshExtras, prelude::*, scene::SceneInstanceReady };
-use serde::{Deserialize, Serialize};
+use bevy::{ camera::primitives::Aabb, ecs::system::SystemParam, gltf::{GltfMeshExtras, GltfMeshName}, prelude::*, scene::SceneInstanceReady };
+use bevy_egui::{EguiContexts, egui};
+use rusty_physics::extras::{BCollider, BJoint, BJointKind, BMeshExtras, BRigidBody, ExtrasError, ExtrasIssue, ExtrasShape, GameLayer, MeshBounds, PrimitiveShape, ShapeContext};
+
+pub fn entity_pipeline_plugin(
+    app: &mut App,
+) {
+    app
+        .init_resource::<ExtrasDiagnostics>()
+        .init_resource::<SceneLoadProgress>()
+        .init_resource::<ColliderCache>()
+        .add_message::<ScenePhysicsReady>()
+        .add_observer(track_scene_spawn)
+        .add_observer(track_scene_ready)
+        .add_systems(Update, (update_scene_load_progress, break_joints));
+
+    #[cfg(feature = "hot_reload")]
+    app.add_plugins(hot_reload::hot_reload_plugin);
+}
+
+#[derive(Component, Clone)]
+pub struct StructureBlock;
+
+/// Force (N) above which the joint on this entity is disabled, set from `BJoint::break_force`
+#[derive(Component)]
+pub struct JointBreakForce(pub f32);
+
+/// Mass given to Cuboid blocks when the extras set neither `mass` nor `density`
+const DEFAULT_BLOCK_MASS: f32 = 100.0;
+
+/// Turns the bevy-free `BMeshExtras` into physics components
+trait MeshExtrasExt {
+    fn insert_material(&self, entity: &mut EntityCommands) -> Vec<ExtrasIssue>;
+    fn rigid_body(&self) -> RigidBody;
+    fn collision_layers(&self) -> Option<CollisionLayers>;
+    fn shape_collider(&self, context: ShapeContext) -> Result<Collider, ExtrasIssue>;
+}
+
+impl MeshExtrasExt for BMeshExtras {
+    /// Inserts the optional physics material components (friction, restitution, mass, damping, gravity)
+    /// - Out of range values are skipped and returned so they can be reported
+    fn insert_material(&self, entity: &mut EntityCommands) -> Vec<ExtrasIssue> {
+        let mut issues = Vec::new();
+        let mut check = |field: &'static str, value: Option<f32>, valid: fn(f32) -> bool, expected: &'static str| {
+            let value = value?;
+            if valid(value) {
+                Some(value)
+            } else {
+                issues.push(ExtrasIssue::InvalidValue { field, value, expected });
+                None
+            }
+        };
+        let non_negative = |value: f32| value >= 0.0;
+        let positive = |value: f32| value > 0.0;
+
+        if let Some(friction) = check("friction", self.friction, non_negative, ">= 0") {
+            entity.insert(Friction::new(friction));
+        }
+        if let Some(restitution) = check("restitution", self.restitution, |value| (0.0..=1.0).contains(&value), "between 0 and 1") {
+            entity.insert(Restitution::new(restitution));
+        }
+        if let Some(density) = check("density", self.density, positive, "> 0") {
+            entity.insert(ColliderDensity(density));
+        }
+        if let Some(mass) = check("mass", self.mass, positive, "> 0") {
+            entity.insert(Mass(mass));
+        }
+        if let Some(damping) = check("linear_damping", self.linear_damping, non_negative, ">= 0") {
+            entity.insert(LinearDamping(damping));
+        }
+        if let Some(damping) = check("angular_damping", self.angular_damping, non_negative, ">= 0") {
+            entity.insert(AngularDamping(damping));
+        }
+        if let Some(scale) = check("gravity_scale", self.gravity_scale, |value| value.is_finite(), "a finite number") {
+            entity.insert(GravityScale(scale));
+        }
+        issues
+    }
+
+    fn rigid_body(&self) -> RigidBody {
+        match self.rigid_body {
+            BRigidBody::Static => RigidBody::Static,
+            BRigidBody::Dynamic => RigidBody::Dynamic,
+            BRigidBody::Kinematic => RigidBody::Kinematic,
+        }
+    }
+
+    /// Collision layers built from the `layers`/`mask` extras, `None` when neither is set
+    fn collision_layers(&self) -> Option<CollisionLayers> {
+        if self.layers.is_none() && self.mask.is_none() {
+            return None;
+        }
+        let to_mask = |layers: &[GameLayer]| LayerMask(layers.iter().fold(0, |bits, layer| bits | layer.to_bits()));
+        let memberships = self.layers.as_deref().map_or(LayerMask::DEFAULT, to_mask);
+        let filters = self.mask.as_deref().map_or(LayerMask::ALL, to_mask);
+        Some(CollisionLayers::new(memberships, filters))
+    }
+
+    /// Builds the collider for every shape that isn't computed from the mesh
+    fn shape_collider(&self, context: ShapeContext) -> Result<Collider, ExtrasIssue> {
+        Ok(match self.shape(context)? {
+            // Mesh bounds that aren't centred on the node origin need the derived shape moved onto them
+            ExtrasShape::Primitive { shape, center } if center != [0.0; 3] => {
+                Collider::compound(vec![(Vec3::from(center), Quat::IDENTITY, primitive_collider(shape))])
+            }
+            ExtrasShape::Primitive { shape, .. } => primitive_collider(shape),
+            ExtrasShape::Compound(parts) => Collider::compound(
+                parts
+                    .into_iter()
+                    .map(|part| {
+                        let [x, y, z] = part.rotation.map(f32::to_radians);
+                        (Vec3::from(part.translation), Quat::from_euler(EulerRot::XYZ, x, y, z), primitive_collider(part.shape))
+                    })
+                    .collect(),
+            ),
+        })
+    }
+}
+
+/// Builds the avian3d collider of a resolved primitive shape
+fn primitive_collider(shape: PrimitiveShape) -> Collider {
+    match shape {
+        PrimitiveShape::Cuboid { size: [x, y, z] } => Collider::cuboid(x, y, z),
+        PrimitiveShape::Sphere { radius } => Collider::sphere(radius),
+        PrimitiveShape::Cylinder { radius, height } => Collider::cylinder(radius, height),
+        PrimitiveShape::Capsule { radius, length } => Collider::capsule(radius, length),
+        PrimitiveShape::Cone { radius, height } => Collider::cone(radius, height),
+    }
+}
+
+/// Physics side of the bevy-free `BCollider`
+trait ColliderKindExt {
+    fn mesh_constructor(&self) -> Option<ColliderConstructor>;
+    fn mesh_collider(&self, mesh: &Mesh) -> Option<Collider>;
+    fn debug_color(&self) -> Color;
+}
+
+impl ColliderKindExt for BCollider {
+    /// Collider constructor for the shapes computed from the mesh itself, `None` for primitives
+    fn mesh_constructor(&self) -> Option<ColliderConstructor> {
+        match self {
+            BCollider::TrimeshFromMesh => Some(ColliderConstructor::TrimeshFromMesh),
+            BCollider::ConvexHull => Some(ColliderConstructor::ConvexHullFromMesh),
+            BCollider::ConvexDecompositionFromMesh => Some(ColliderConstructor::ConvexDecompositionFromMesh),
+            _ => None,
+        }
+    }
+
+    /// Builds a mesh based collider directly (what `ColliderConstructor` would do) so it can be cached
+    fn mesh_collider(&self, mesh: &Mesh) -> Option<Collider> {
+        match self {
+            BCollider::TrimeshFromMesh => Collider::trimesh_from_mesh(mesh),
+            BCollider::ConvexHull => Collider::convex_hull_from_mesh(mesh),
+            BCollider::ConvexDecompositionFromMesh => Collider::convex_decomposition_from_mesh(mesh),
+            _ => None,
+        }
+    }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct BMeshExtras {
-    pub collider: BCollider,
-    pub rigid_body: BRigidBody,
-    pub cube_size: Option<Vec3>,
-    pub radius: Option<f32>,
-    pub height: Option<f32>,
+    /// Colour used by the physics debug renderer for each collider kind
+    fn debug_color(&self) -> Color {
+        match self {
+            BCollider::TrimeshFromMesh => Color::srgb(0.0, 0.0, 1.0),
+            BCollider::ConvexHull => Color::srgb(1.0, 1.0, 1.0),
+            BCollider::ConvexDecompositionFromMesh => Color::srgb(0.5, 0.5, 1.0),
+            BCollider::Cuboid => Color::srgb(0.0, 1.0, 0.0),
+            BCollider::Sphere => Color::srgb(1.0, 0.0, 0.0),
+            BCollider::Cylinder => Color::srgb(1.0, 0.0, 1.0),
+            BCollider::Capsule => Color::srgb(1.0, 0.5, 0.0),
+            BCollider::Cone => Color::srgb(0.0, 1.0, 1.0),
+            BCollider::Compound => Color::srgb(0.5, 1.0, 0.5),
+        }
+    }
+}
+
+/// Creates the avian3d joint of a bevy-free `BJoint`
+trait JointExt {
+    fn spawn(&self, commands: &mut Commands, scene: Entity, body: Entity, target: Entity) -> Result<(), ExtrasIssue>;
+}
+
+impl JointExt for BJoint {
+    /// Spawns the joint between `body` and the resolved `target` body as a child of the scene instance
+    fn spawn(&self, commands: &mut Commands, scene: Entity, body: Entity, target: Entity) -> Result<(), ExtrasIssue> {
+        let (anchor, target_anchor) = (Vec3::from(self.anchor), Vec3::from(self.target_anchor));
+        let axis = self.axis.map(Vec3::from);
+        let invalid = |message: &str| ExtrasIssue::InvalidJoint { target: self.target.clone(), message: message.to_string() };
+        let mut joint = commands.spawn((ChildOf(scene), JointCollisionDisabled));
+        match self.kind {
+            BJointKind::Fixed => {
+                joint.insert(FixedJoint::new(body, target)
+                    .with_local_anchor1(anchor)
+                    .with_local_anchor2(target_anchor));
+            }
+            BJointKind::Revolute => {
+                let mut revolute = RevoluteJoint::new(body, target)
+                    .with_local_anchor1(anchor)
+                    .with_local_anchor2(target_anchor)
+                    .with_hinge_axis(axis.unwrap_or(RevoluteJoint::DEFAULT_HINGE_AXIS));
+                if let Some([min, max]) = self.limits {
+                    revolute = revolute.with_angle_limits(min, max);
+                }
+                joint.insert(revolute);
+            }
+            BJointKind::Spherical => {
+                let mut spherical = SphericalJoint::new(body, target)
+                    .with_local_anchor1(anchor)
+                    .with_local_anchor2(target_anchor);
+                if let Some(axis) = axis {
+                    spherical = spherical.with_twist_axis(axis);
+                }
+                if let Some([min, max]) = self.limits {
+                    spherical = spherical.with_swing_limits(min, max);
+                }
+                joint.insert(spherical);
+            }
+            BJointKind::Prismatic => {
+                let mut prismatic = PrismaticJoint::new(body, target)
+                    .with_local_anchor1(anchor)
+                    .with_local_anchor2(target_anchor);
+                if let Some(axis) = axis {
+                    prismatic = prismatic.with_slider_axis(axis);
+                }
+                if let Some([min, max]) = self.limits {
+                    prismatic = prismatic.with_limits(min, max);
+                }
+                joint.insert(prismatic);
+            }
+            BJointKind::Distance => {
+                // The rest length defaults to 0 which would pull both bodies together, so limits are required
+                let Some([min, max]) = self.limits else {
+                    joint.despawn();
+                    return Err(invalid("is a Distance joint without limits"));
+                };
+                joint.insert(DistanceJoint::new(body, target)
+                    .with_local_anchor1(anchor)
+                    .with_local_anchor2(target_anchor)
+                    .with_limits(min, max));
+            }
+        }
+        if let Some(break_force) = self.break_force {
+            joint.insert((JointForces::new(), JointBreakForce(break_force)));
+        }
+        Ok(())
+    }
+}
+
+/// Collects every extras error found while spawning scenes so they can be shown in-game
+#[derive(Resource, Default)]
+pub struct ExtrasDiagnostics(pub Vec<ExtrasError>);
+
+impl ExtrasDiagnostics {
+    /// Logs the error and records it, ignoring duplicates from repeated spawns of the same scene
+    pub fn report(&mut self, error: ExtrasError) {
+        if self.0.contains(&error) {
+            return;
+        }
+        error!("Invalid physics extras: {}", error);
+        self.0.push(error);
+    }
+}
+
+/// Colliders already built for a mesh and extras combination
+/// - Repeated scene spawns (towers, falling cubes) reuse them instead of rebuilding trimeshes and hulls
+/// - Colliders are cheap to clone, the shape itself is shared
+#[derive(Resource, Default)]
+pub struct ColliderCache {
+    entries: HashMap<ColliderCacheKey, CachedCollider>,
+    pub metrics: ColliderCacheMetrics,
+}
+
+#[derive(PartialEq, Eq, Hash)]
+struct ColliderCacheKey {
+    mesh: Option<AssetId<Mesh>>,
+    extras: String,
+    /// Bits of the node's accumulated scale, primitive sizes are divided by it
+    scale: [u32; 3],
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub enum BCollider {
-    TrimeshFromMesh,
-    ConvexHull,
-    Cuboid,
-    Sphere,
-    Cylinder,
+struct CachedCollider {
+    collider: Collider,
+    build_time: Duration,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub enum BRigidBody {
-    Static,
-    Dynamic,
+/// Spawn-time numbers showing how much work the collider cache saves
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ColliderCacheMetrics {
+    pub hits: u32,
+    pub misses: u32,
+    /// Time spent building colliders that weren't cached yet
+    pub build_time: Duration,
+    /// Time the cache hits would have spent building their collider again
+    pub saved_time: Duration,
+    /// Time spent applying physics to spawned scenes, colliders included
+    pub process_time: Duration,
 }
 
+impl ColliderCache {
+    /// Returns the cached collider for `key`, building and storing it on a miss
+    /// - Failed builds aren't cached so every instance still reports its error
+    fn get_or_build<E>(
+        &mut self,
+        key: ColliderCacheKey,
+        build: impl FnOnce() -> Result<Collider, E>,
+    ) -> Result<Collider, E> {
+        if let Some(cached) = self.entries.get(&key) {
+            self.metrics.hits += 1;
+            self.metrics.saved_time += cached.build_time;
+            return Ok(cached.collider.clone());
+        }
+        let start = Instant::now();
+        let collider = build()?;
+        let build_time = start.elapsed();
+        self.metrics.misses += 1;
+        self.metrics.build_time += build_time;
+        self.entries.insert(key, CachedCollider { collider: collider.clone(), build_time });
+        Ok(collider)
+    }
+}
+
+/// Tags the `SceneRoot`s whose loading is tracked by `SceneLoadProgress` (e.g. the scenes of a level with a loading screen)
 #[derive(Component)]
-pub struct StructureBlock;
+pub struct TrackLoading;
+
+/// Keeps count of the `TrackLoading` scenes spawned since the last time every one of them was ready
+/// - `processed + failed` out of `total` can be shown as loading progress
+/// - With no tracked scene at all, loading is complete straight away
+#[derive(Resource, Default)]
+pub struct SceneLoadProgress {
+    pending: HashSet<Entity>,
+    /// Pending scenes whose instance is spawned and went through the pipeline observers
+    instanced: HashSet<Entity>,
+    pub processed: usize,
+    pub failed: usize,
+    pub total: usize,
+}
+
+impl SceneLoadProgress {
+    /// True once every tracked scene has its colliders built, or failed to load
+    pub fn is_complete(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+/// Sent once every pending `TrackLoading` scene has its colliders built, or failed to load
+#[derive(Message)]
+pub struct ScenePhysicsReady {
+    pub scenes: usize,
+    pub failed: usize,
+}
+
+/// Queries needed to walk a spawned scene and describe where a mesh came from
+#[derive(SystemParam)]
+pub struct GltfPhysicsQueries<'w, 's> {
+    children: Query<'w, 's, &'static Children>,
+    extras: Query<'w, 's, &'static GltfMeshExtras>,
+    mesh_names: Query<'w, 's, &'static GltfMeshName>,
+    names: Query<'w, 's, &'static Name>,
+    parents: Query<'w, 's, &'static ChildOf>,
+    scene_roots: Query<'w, 's, &'static SceneRoot>,
+    transforms: Query<'w, 's, &'static Transform>,
+    aabbs: Query<'w, 's, &'static Aabb>,
+    meshes: Query<'w, 's, &'static Mesh3d>,
+    mesh_assets: Res<'w, Assets<Mesh>>,
+    diagnostics: ResMut<'w, ExtrasDiagnostics>,
+    cache: ResMut<'w, ColliderCache>,
+}
+
+impl GltfPhysicsQueries<'_, '_> {
+    /// Product of the local scales from the entity up to the root of its scene instance
+    /// - The `SceneRoot` scale is left out, it belongs to the instance and avian3d applies it on top of the collider
+    /// - `GlobalTransform` isn't propagated yet when a scene instance becomes ready, so it can't be used here
+    fn scene_scale(&self, entity: Entity) -> Vec3 {
+        let mut scale = self.transforms.get(entity).map_or(Vec3::ONE, |transform| transform.scale);
+        for ancestor in self.parents.iter_ancestors(entity) {
+            if self.scene_roots.contains(ancestor) {
+                break;
+            }
+            if let Ok(transform) = self.transforms.get(ancestor) {
+                scale *= transform.scale;
+            }
+        }
+        scale
+    }
+
+    /// Cache key for the collider of a mesh primitive, its extras string and scale within the scene fully determine the shape
+    /// - Instances of a scene share their colliders whatever scale they are spawned at
+    fn cache_key(&self, entity: Entity, extras: &str, scale: Vec3) -> ColliderCacheKey {
+        ColliderCacheKey {
+            mesh: self.meshes.get(entity).ok().map(|mesh| mesh.id()),
+            extras: extras.to_string(),
+            scale: scale.to_array().map(f32::to_bits),
+        }
+    }
+
+    /// Name of the glTF node a mesh primitive was spawned under
+    fn node_name(&self, entity: Entity) -> Option<String> {
+        // Mesh primitives are spawned as children of the glTF node, which holds the node name
+        self.parents
+            .get(entity)
+            .ok()
+            .and_then(|child_of| self.names.get(child_of.parent()).ok())
+            .map(|name| name.to_string())
+    }
+
+    /// Ties an extras issue to the scene/node/mesh it was found on
+    fn describe(&self, scene: Entity, entity: Entity, issue: &ExtrasIssue) -> ExtrasError {
+        let scene = self
+            .scene_roots
+            .get(scene)
+            .ok()
+            .and_then(|root| root.0.path())
+            .map(|path| path.to_string())
+            .unwrap_or_else(|| format!("{:?}", scene));
+        let node = self
+            .node_name(entity)
+            .unwrap_or_else(|| format!("{:?}", entity));
+        let mesh = self
+            .mesh_names
+            .get(entity)
+            .map(|name| name.0.clone())
+            .unwrap_or_default();
+        ExtrasError::new(scene, node, mesh, issue)
+    }
+}
 
 /// Process all entities within an newly loaded scene instance
 /// and apply physics components based on GLTF extras
+/// - Meshes with invalid extras are reported to `ExtrasDiagnostics` instead of panicking
+/// - A node that can't be parsed is skipped, a node missing a required size falls back to a convex hull
+/// - Colliders come from `ColliderCache` when the same mesh and extras were already processed
 pub fn process_gltf_descendants(
     trigger_entity: Entity,
     mut commands: Commands,
-    children: Query<&Children>,
-    extras: &Query<&GltfMeshExtras>,
+    mut queries: GltfPhysicsQueries,
 ) {
     info!("Processing scene descendants for entity {:?}", trigger_entity);
+    let start = Instant::now();
+    let metrics_before = queries.cache.metrics;
 
     // Iterate through the scene to check entities
-    for entity in children.iter_descendants(trigger_entity) {
+    let descendants: Vec<Entity> = queries.children.iter_descendants(trigger_entity).collect();
+    // Node name -> physics body, used to resolve joint targets once every body of the scene is known
+    let mut bodies: HashMap<String, Entity> = HashMap::new();
+    let mut pending_joints: Vec<(Entity, Vec<BJoint>)> = Vec::new();
+    for entity in descendants {
         // If the entity has a GltfMeshExtras component, apply physics
-        let Ok(gltf_mesh_extras) = extras.get(entity) else {
+        let Ok(gltf_mesh_extras) = queries.extras.get(entity) else {
             continue;
         };
-        let Ok(data) = serde_json::from_str::<BMeshExtras>(&gltf_mesh_extras.value) else {
-            error!("Couldn't deserialize extras!");
+        let mut data = match BMeshExtras::parse(&gltf_mesh_extras.value) {
+            Ok(data) => data,
+            Err(issue) => {
+                let error = queries.describe(trigger_entity, entity, &issue);
+                queries.diagnostics.report(error);
+                continue;
+            }
+        };
+
+        if let Some(node) = queries.node_name(entity) {
+            bodies.insert(node, entity);
+        }
+        if !data.joints.is_empty() {
+            pending_joints.push((entity, std::mem::take(&mut data.joints)));
+        }
+
+        // Physics material is applied whichever collider ends up on the node
+        for issue in data.insert_material(&mut commands.entity(entity)) {
+            let error = queries.describe(trigger_entity, entity, &issue);
+            queries.diagnostics.report(error);
+        }
+
+        // Sensors report collisions without a contact response, so they also need collision events to be useful as triggers
+        if data.sensor {
+            commands.entity(entity).insert((Sensor, CollisionEventsEnabled));
+        }
+        if let Some(layers) = data.collision_layers() {
+            commands.entity(entity).insert(layers);
+        }
+
+        let debug_render = DebugRender::default().with_collider_color(data.collider.debug_color());
+        let scale = queries.scene_scale(entity);
+        if let Some(constructor) = data.collider.mesh_constructor() {
+            // Mesh colliders are built in mesh space, avian3d scales them with the node
+            let key = queries.cache_key(entity, &gltf_mesh_extras.value, Vec3::ONE);
+            let mesh = queries.meshes.get(entity).ok().and_then(|mesh| queries.mesh_assets.get(mesh));
+            let collider = match mesh {
+                Some(mesh) => queries.cache.get_or_build(key, || data.collider.mesh_collider(mesh).ok_or(())).ok(),
+                None => None,
+            };
+            // Let avian3d try (and report) the meshes that couldn't be built here
+            match collider {
+                Some(collider) => commands.entity(entity).insert((data.rigid_body(), collider, debug_render)),
+                None => commands.entity(entity).insert((data.rigid_body(), constructor, debug_render)),
+            };
             continue;
+        }
+
+        let context = ShapeContext {
+            scale: scale.to_array(),
+            bounds: queries.aabbs.get(entity).ok().map(|aabb| MeshBounds {
+                center: aabb.center.to_array(),
+                half_extents: aabb.half_extents.to_array(),
+            }),
+        };
+        let key = queries.cache_key(entity, &gltf_mesh_extras.value, scale);
+        let collider = match queries.cache.get_or_build(key, || data.shape_collider(context)) {
+            Ok(collider) => collider,
+            Err(issue) => {
+                insert_fallback_collider(&mut commands, &mut queries, trigger_entity, entity, data.rigid_body(), issue);
+                continue;
+            }
         };
+        commands.entity(entity).insert((
+            data.rigid_body(),
+            collider,
+            debug_render,
+        ));
+        if let BCollider::Cuboid = data.collider {
+            commands.entity(entity).insert((
+                StructureBlock,
+                CenterOfMass::default(),
+            ));
+            if data.mass.is_none() && data.density.is_none() {
+                commands.entity(entity).insert(Mass(DEFAULT_BLOCK_MASS));
+            }
+        }
+    }
 
-        match data.collider {
-            BCollider::TrimeshFromMesh => {
-                commands.entity(entity).insert((
-                    match data.rigid_body {
-                        BRigidBody::Static => RigidBody::Static,
-                        BRigidBody::Dynamic => RigidBody::Dynamic,
-                    },
-                    ColliderConstructor::TrimeshFromMesh,
-                    DebugRender::default().with_collider_color(Color::srgb(0.0, 0.0, 1.0)),
-                ));
-            }
-            BCollider::ConvexHull => {
-                commands.entity(entity).insert((
-                    match data.rigid_body {
-                        BRigidBody::Static => RigidBody::Static,
-                        BRigidBody::Dynamic => RigidBody::Dynamic,
-                    },
-                    ColliderConstructor::ConvexHullFromMesh,
-                    DebugRender::default().with_collider_color(Color::srgb(1.0, 1.0, 1.0)),
-                ));
-            }
-            BCollider::Cuboid => {
-                let size = data.cube_size.expect(
-                    "Cuboid collider must have cube_size",
-                );
-                // Scale the defined size by the entity's scale to avoid wrong collider size
-                let scaled_size = size * 2.0;
-                commands.entity(entity).insert((
-                    match data.rigid_body {
-                        BRigidBody::Static => RigidBody::Static,
-                        BRigidBody::Dynamic => RigidBody::Dynamic,
-                    },
-                    StructureBlock,
-                    Mass(100.0),
-                    CenterOfMass::default(),
-                    Collider::cuboid(scaled_size.x, scaled_size.y, scaled_size.z),
-                    DebugRender::default().with_collider_color(Color::srgb(0.0, 1.0, 0.0)),
-                ));
-            }
-            BCollider::Sphere => {
-                let size = data.radius.expect(
-                    "Sphere collider must have sphere_radius"
-                );
-                commands.entity(entity).insert((
-                    match data.rigid_body {
-                        BRigidBody::Static => RigidBody::Static,
-                        BRigidBody::Dynamic => RigidBody::Dynamic,
-                    },
-                    Collider::sphere(size),
-                    DebugRender::default().with_collider_color(Color::srgb(1.0, 0.0, 0.0)),
-                ));
-            }
-            BCollider::Cylinder => {
-                let radius = data.radius.expect(
-                    "Cylinder collider must have radius"
-                );
-                let height = data.height.expect(
-                    "Cylinder collider must have height"
-                );
-                commands.entity(entity).insert((
-                    match data.rigid_body {
-                        BRigidBody::Static => RigidBody::Static,
-                        BRigidBody::Dynamic => RigidBody::Dynamic,
-                    },
-                    Collider::cylinder(radius, height*2.0),
-                    DebugRender::default().with_collider_color(Color::srgb(1.0, 0.0, 1.0)),
-                ));
+    // Joints are resolved by node name inside this scene instance only
+    for (entity, joints) in pending_joints {
+        for joint in joints {
+            let result = match bodies.get(&joint.target) {
+                Some(&target) => joint.spawn(&mut commands, trigger_entity, entity, target),
+                None => Err(ExtrasIssue::InvalidJoint { target: joint.target.clone(), message: "targets an unknown node".to_string() }),
+            };
+            if let Err(issue) = result {
+                let error = queries.describe(trigger_entity, entity, &issue);
+                queries.diagnostics.report(error);
             }
         }
     }
+
+    let elapsed = start.elapsed();
+    let metrics = &mut queries.cache.metrics;
+    metrics.process_time += elapsed;
+    info!(
+        "Scene {:?} physics applied in {:?}: {} cached collider(s), {} built",
+        trigger_entity,
+        elapsed,
+        metrics.hits - metrics_before.hits,
+        metrics.misses - metrics_before.misses,
+    );
+}
+
+/// Reports an unusable shape and gives the node a mesh based collider so it still takes part in the simulation
+fn insert_fallback_collider(
+    commands: &mut Commands,
+    queries: &mut GltfPhysicsQueries,
+    scene: Entity,
+    entity: Entity,
+    rigid_body: RigidBody,
+    issue: ExtrasIssue,
+) {
+    let error = queries.describe(scene, entity, &issue);
+    queries.diagnostics.report(error);
+    commands.entity(entity).insert((
+        rigid_body,
+        ColliderConstructor::ConvexHullFromMesh,
+        DebugRender::default().with_collider_color(Color::srgb(1.0, 1.0, 0.0)),
+    ));
 }
 
 /// A dedicated observer system for the initial, one-time level setup (Scene 1).
 pub fn on_level_scene_spawn(
     trigger: On<SceneInstanceReady>,
     commands: Commands,
-    children: Query<&Children>,
-    extras: Query<&GltfMeshExtras>,
+    queries: GltfPhysicsQueries,
 ) {
     info!("LEVEL SCENE READY: Running physics setup for the main level. (ONE TIME)");
     process_gltf_descendants(
         trigger.entity,
         commands,
-        children,
-        &extras,
+        queries,
     );
 }
 
@@ -142,15 +590,13 @@ pub fn on_level_scene_spawn(
 pub fn on_structure_scene_spawn(
     trigger: On<SceneInstanceReady>,
     commands: Commands,
-    children: Query<&Children>,
-    extras: Query<&GltfMeshExtras>,
+    queries: GltfPhysicsQueries,
 ) {
     info!("STRUCTURE SCENE READY: Running physics setup for a new shape.");
     process_gltf_descendants(
         trigger.entity,
         commands,
-        children,
-        &extras,
+        queries,
     );
 }
 
@@ -158,14 +604,250 @@ pub fn on_structure_scene_spawn(
 pub fn on_shape_scene_spawn(
     trigger: On<SceneInstanceReady>,
     commands: Commands,
-    children: Query<&Children>,
-    extras: Query<&GltfMeshExtras>,
+    queries: GltfPhysicsQueries,
 ) {
     info!("SHAPE SCENE READY: Running physics setup for a new shape.");
     process_gltf_descendants(
         trigger.entity,
         commands,
-        children,
-        &extras,
+        queries,
     );
 }
+
+/// Starts tracking every new `TrackLoading` scene, a new batch starts once the previous one is complete
+fn track_scene_spawn(
+    trigger: On<Add, TrackLoading>,
+    mut progress: ResMut<SceneLoadProgress>,
+) {
+    if progress.is_complete() {
+        progress.processed = 0;
+        progress.failed = 0;
+        progress.total = 0;
+    }
+    if progress.pending.insert(trigger.entity) {
+        progress.total += 1;
+    }
+}
+
+/// Marks a scene as instanced, the per-scene pipeline observers queue its physics components in the same trigger
+fn track_scene_ready(
+    trigger: On<SceneInstanceReady>,
+    mut progress: ResMut<SceneLoadProgress>,
+) {
+    if progress.pending.contains(&trigger.entity) {
+        progress.instanced.insert(trigger.entity);
+    }
+}
+
+/// Settles the pending scenes and sends `ScenePhysicsReady` once the last one is settled
+/// - A scene is processed once no `ColliderConstructor` is left in it, avian3d either built the collider or reported why it couldn't
+/// - Scenes that failed to load are counted and logged, scenes despawned while loading (e.g. a level reset) are dropped
+fn update_scene_load_progress(
+    asset_server: Res<AssetServer>,
+    mut progress: ResMut<SceneLoadProgress>,
+    roots: Query<&SceneRoot, With<TrackLoading>>,
+    children: Query<&Children>,
+    constructors: Query<(), With<ColliderConstructor>>,
+    mut ready: MessageWriter<ScenePhysicsReady>,
+) {
+    if progress.is_complete() {
+        return;
+    }
+    let pending: Vec<Entity> = progress.pending.iter().copied().collect();
+    for entity in pending {
+        let Ok(root) = roots.get(entity) else {
+            progress.total -= 1;
+            progress.pending.remove(&entity);
+            progress.instanced.remove(&entity);
+            continue;
+        };
+        if asset_server.load_state(&root.0).is_failed() {
+            error!("Scene {:?} failed to load, it won't get any physics", root.0.path());
+            progress.failed += 1;
+        } else if progress.instanced.contains(&entity)
+            && !children.iter_descendants(entity).any(|descendant| constructors.contains(descendant))
+        {
+            progress.processed += 1;
+        } else {
+            continue;
+        }
+        progress.pending.remove(&entity);
+        progress.instanced.remove(&entity);
+    }
+    if progress.is_complete() {
+        info!("All {} scene(s) are physics ready, {} failed to load", progress.processed, progress.failed);
+        ready.write(ScenePhysicsReady { scenes: progress.processed, failed: progress.failed });
+    }
+}
+
+/// Disables joints authored with a `break_force` once the force they apply exceeds it
+fn break_joints(
+    mut commands: Commands,
+    joints: Query<(Entity, &JointForces, &JointBreakForce), Without<JointDisabled>>,
+) {
+    for (entity, forces, break_force) in &joints {
+        if forces.force().length() > break_force.0 {
+            commands.entity(entity).insert(JointDisabled);
+        }
+    }
+}
+
+/// Shows how many colliders were reused from `ColliderCache` and the build time that saved
+pub fn collider_cache_panel(
+    mut contexts: EguiContexts,
+    mut cache: ResMut<ColliderCache>,
+) -> Result {
+    egui::Window::new("Collider Cache")
+        .resizable(false)
+        .default_open(false)
+        .show(contexts.ctx_mut()?, |ui| {
+            let metrics = cache.metrics;
+            ui.label(format!("Cached colliders: {}", cache.entries.len()));
+            ui.label(format!("Hits: {} / Misses: {}", metrics.hits, metrics.misses));
+            ui.label(format!("Build time: {:.2?}", metrics.build_time));
+            ui.label(format!("Saved build time: {:.2?}", metrics.saved_time));
+            ui.label(format!("Scene processing time: {:.2?}", metrics.process_time));
+            if ui.button("Reset metrics").clicked() {
+                cache.metrics = ColliderCacheMetrics::default();
+            }
+        });
+    Ok(())
+}
+
+/// Lists every extras error collected so far, only shown when there is something to report
+pub fn extras_diagnostics_panel(
+    mut contexts: EguiContexts,
+    mut diagnostics: ResMut<ExtrasDiagnostics>,
+) -> Result {
+    if diagnostics.0.is_empty() {
+        return Ok(());
+    }
+    egui::Window::new("Physics Extras Diagnostics")
+        .resizable(true)
+        .vscroll(true)
+        .default_open(true)
+        .show(contexts.ctx_mut()?, |ui| {
+            ui.label(format!("{} mesh(es) have invalid physics extras:", diagnostics.0.len()));
+            ui.separator();
+            for error in &diagnostics.0 {
+                ui.label(format!("Scene: {}", error.scene));
+                ui.label(format!("Node: {} (mesh {})", error.node, error.mesh));
+                if let Some(field) = &error.field {
+                    ui.label(format!("Field: {}", field));
+                }
+                ui.label(egui::RichText::new(&error.message).color(egui::Color32::LIGHT_RED));
+                ui.separator();
+            }
+            if ui.button("Clear").clicked() {
+                diagnostics.0.clear();
+            }
+        });
+    Ok(())
+}
+
+/// Re-applies physics to scenes whose glb changed on disk, enabled with the `hot_reload` feature
+/// - Bevy respawns modified scenes in place and triggers `SceneInstanceReady` again, which reruns the pipeline observers
+/// - Bodies are put back where they were, stale colliders and errors are dropped so they get rebuilt and reported again
+#[cfg(feature = "hot_reload")]
+mod hot_reload {
+    use std::collections::HashMap;
+
+    use avian3d::prelude::RigidBody;
+    use bevy::{gltf::GltfMeshName, prelude::*, scene::SceneInstanceReady};
+
+    use super::{ColliderCache, ExtrasDiagnostics};
+
+    pub fn hot_reload_plugin(
+        app: &mut App,
+    ) {
+        app
+            .init_resource::<ReloadedTransforms>()
+            .add_observer(restore_transforms)
+            .add_systems(Update, (invalidate_colliders, capture_transforms));
+    }
+
+    /// Body transforms of the scene instances about to be respawned
+    /// - Keyed by scene root, then by node and mesh name since the respawned entities are new
+    #[derive(Resource, Default)]
+    struct ReloadedTransforms(HashMap<Entity, HashMap<(String, String), Transform>>);
+
+    impl ColliderCache {
+        /// Drops the colliders built from a mesh that has been reloaded
+        fn invalidate(&mut self, mesh: AssetId<Mesh>) {
+            self.entries.retain(|key, _| key.mesh != Some(mesh));
+        }
+    }
+
+    /// Reloaded glb files keep their mesh handles, so the cached colliders for them are stale
+    fn invalidate_colliders(
+        mut events: MessageReader<AssetEvent<Mesh>>,
+        mut cache: ResMut<ColliderCache>,
+    ) {
+        for event in events.read() {
+            if let AssetEvent::Modified { id } = event {
+                cache.invalidate(*id);
+            }
+        }
+    }
+
+    /// Remembers where the bodies of a modified scene are before Bevy respawns it in `SpawnScene` (after `Update`)
+    fn capture_transforms(
+        mut events: MessageReader<AssetEvent<Scene>>,
+        roots: Query<(Entity, &SceneRoot)>,
+        children: Query<&Children>,
+        bodies: Query<(&Transform, &ChildOf, &GltfMeshName), With<RigidBody>>,
+        names: Query<&Name>,
+        mut reloaded: ResMut<ReloadedTransforms>,
+        mut diagnostics: ResMut<ExtrasDiagnostics>,
+    ) {
+        for event in events.read() {
+            let AssetEvent::Modified { id } = event else {
+                continue;
+            };
+            for (root, scene_root) in &roots {
+                if scene_root.id() != *id {
+                    continue;
+                }
+                if let Some(path) = scene_root.0.path() {
+                    info!("{} changed on disk, respawning instance {:?}", path, root);
+                    // Errors of the old version are reported again (or not, once fixed) when the scene is processed
+                    let path = path.to_string();
+                    diagnostics.0.retain(|error| error.scene != path);
+                }
+                let transforms = children
+                    .iter_descendants(root)
+                    .filter_map(|entity| {
+                        let (transform, child_of, mesh_name) = bodies.get(entity).ok()?;
+                        let node = names.get(child_of.parent()).ok()?;
+                        Some(((node.to_string(), mesh_name.0.clone()), *transform))
+                    })
+                    .collect();
+                reloaded.0.insert(root, transforms);
+            }
+        }
+    }
+
+    /// Puts the bodies of a respawned scene back where they were before the reload
+    fn restore_transforms(
+        trigger: On<SceneInstanceReady>,
+        mut reloaded: ResMut<ReloadedTransforms>,
+        children: Query<&Children>,
+        names: Query<&Name>,
+        mut bodies: Query<(&mut Transform, &ChildOf, &GltfMeshName)>,
+    ) {
+        let Some(transforms) = reloaded.0.remove(&trigger.entity) else {
+            return;
+        };
+        for entity in children.iter_descendants(trigger.entity) {
+            let Ok((mut transform, child_of, mesh_name)) = bodies.get_mut(entity) else {
+                continue;
+            };
+            let Ok(node) = names.get(child_of.parent()) else {
+                continue;
+            };
+            if let Some(previous) = transforms.get(&(node.to_string(), mesh_name.0.clone())) {
+                *transform = *previous;
+            }
+        }
+    }
+}
@@ -0,0 +1,537 @@
+//! Schema of the physics extras authored on glTF meshes, and the collider sizes derived from them
+//! - Doesn't depend on bevy or avian3d so `lint_extras` can check assets headless, the game turns these into components in `entity_pipeline`
+//! - Vectors are `[x, y, z]` arrays, the same JSON a `Vec3` reads from
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BMeshExtras {
+    pub collider: BCollider,
+    pub rigid_body: BRigidBody,
+    pub cube_size: Option<[f32; 3]>,
+    pub radius: Option<f32>,
+    pub height: Option<f32>,
+    #[serde(default)]
+    pub compound: Vec<BCompoundPart>,
+    pub friction: Option<f32>,
+    pub restitution: Option<f32>,
+    pub density: Option<f32>,
+    pub mass: Option<f32>,
+    pub linear_damping: Option<f32>,
+    pub angular_damping: Option<f32>,
+    pub gravity_scale: Option<f32>,
+    #[serde(default)]
+    pub sensor: bool,
+    pub layers: Option<Vec<GameLayer>>,
+    pub mask: Option<Vec<GameLayer>>,
+    #[serde(default)]
+    pub joints: Vec<BJoint>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum BCollider {
+    TrimeshFromMesh,
+    ConvexHull,
+    /// Splits a concave mesh into convex parts, use this instead of a trimesh for concave dynamic bodies
+    ConvexDecompositionFromMesh,
+    Cuboid,
+    Sphere,
+    Cylinder,
+    Capsule,
+    Cone,
+    /// Combines the primitive colliders listed in `compound`
+    Compound,
+}
+
+/// A primitive collider inside a `Compound` collider
+/// - Sizes follow the same rules as the top-level extras fields
+/// - `translation` and `rotation` (Euler XYZ, degrees) place the part relative to the node
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BCompoundPart {
+    pub collider: BCollider,
+    pub cube_size: Option<[f32; 3]>,
+    pub radius: Option<f32>,
+    pub height: Option<f32>,
+    #[serde(default)]
+    pub translation: [f32; 3],
+    #[serde(default)]
+    pub rotation: [f32; 3],
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum BRigidBody {
+    Static,
+    Dynamic,
+    Kinematic,
+}
+
+/// Named collision layers that can be referenced from the `layers` and `mask` extras
+/// - `layers` are the layers a node belongs to (defaults to `Default`)
+/// - `mask` are the layers it collides with (defaults to all of them)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameLayer {
+    #[default]
+    Default,
+    Ground,
+    Structure,
+    Prop,
+    Debris,
+    Trigger,
+}
+
+impl GameLayer {
+    /// Bit of the layer in a layer mask, in declaration order like avian3d's `PhysicsLayer` derive
+    pub fn to_bits(self) -> u32 {
+        1 << self as u32
+    }
+}
+
+/// A joint authored on a node, connecting it to another node of the same scene instance
+/// - `anchor`/`target_anchor` are local to each body, `axis` is the hinge/slider axis
+/// - `limits` are angles in radians for Revolute/Spherical and distances for Prismatic/Distance
+/// - The joint is disabled once the force it applies exceeds `break_force`
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BJoint {
+    pub kind: BJointKind,
+    pub target: String,
+    #[serde(default)]
+    pub anchor: [f32; 3],
+    #[serde(default)]
+    pub target_anchor: [f32; 3],
+    pub axis: Option<[f32; 3]>,
+    pub limits: Option<[f32; 2]>,
+    pub break_force: Option<f32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum BJointKind {
+    Fixed,
+    Revolute,
+    Spherical,
+    Prismatic,
+    Distance,
+}
+
+/// Describes a single mesh whose extras could not be turned into physics components
+/// - `scene` is the asset path of the scene the mesh was spawned from (e.g. `shapes.glb#Scene3`)
+/// - `field` is the offending extras key, when one could be identified
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtrasError {
+    pub scene: String,
+    pub node: String,
+    pub mesh: String,
+    pub field: Option<String>,
+    pub message: String,
+}
+
+impl ExtrasError {
+    pub fn new(scene: String, node: String, mesh: String, issue: &ExtrasIssue) -> Self {
+        ExtrasError {
+            scene,
+            node,
+            mesh,
+            field: issue.field(),
+            message: issue.message(),
+        }
+    }
+}
+
+impl fmt::Display for ExtrasError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.scene)?;
+        // Scene-wide problems have no node or mesh
+        if !self.node.is_empty() {
+            write!(f, " / {}", self.node)?;
+        }
+        if !self.mesh.is_empty() {
+            write!(f, " (mesh {})", self.mesh)?;
+        }
+        if let Some(field) = &self.field {
+            write!(f, " [{}]", field)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Reason why a `BMeshExtras` could not be used as-is, before it is tied to a scene/node
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExtrasIssue {
+    /// The extras JSON does not match the `BMeshExtras` schema, the node is skipped
+    Parse { field: Option<String>, message: String },
+    /// A size required by the selected collider is missing, the node falls back to a convex hull
+    MissingField { field: &'static str, collider: &'static str },
+    /// A `Compound` collider is empty or one of its parts is not a primitive, the node falls back to a convex hull
+    InvalidCompound { message: String },
+    /// A physics material value is out of range, the property is left at the avian3d default
+    InvalidValue { field: &'static str, value: f32, expected: &'static str },
+    /// A joint could not be created, the other joints of the node are still created
+    InvalidJoint { target: String, message: String },
+}
+
+impl ExtrasIssue {
+    fn field(&self) -> Option<String> {
+        match self {
+            ExtrasIssue::Parse { field, .. } => field.clone(),
+            ExtrasIssue::MissingField { field, .. } | ExtrasIssue::InvalidValue { field, .. } => Some(field.to_string()),
+            ExtrasIssue::InvalidJoint { .. } => Some("joints".to_string()),
+            ExtrasIssue::InvalidCompound { .. } => Some("compound".to_string()),
+        }
+    }
+
+    fn message(&self) -> String {
+        match self {
+            ExtrasIssue::Parse { message, .. } => message.clone(),
+            ExtrasIssue::MissingField { field, collider } => {
+                format!("{} collider must have {}, falling back to ConvexHull", collider, field)
+            }
+            ExtrasIssue::InvalidValue { field, value, expected } => {
+                format!("{} is {} but must be {}, ignoring it", field, value, expected)
+            }
+            ExtrasIssue::InvalidCompound { message } => {
+                format!("{}, falling back to ConvexHull", message)
+            }
+            ExtrasIssue::InvalidJoint { target, message } => {
+                format!("joint to '{}' {}, skipping it", target, message)
+            }
+        }
+    }
+}
+
+impl BMeshExtras {
+    /// Deserializes the raw extras JSON of a glTF mesh
+    pub fn parse(value: &str) -> Result<Self, ExtrasIssue> {
+        serde_json::from_str::<BMeshExtras>(value).map_err(|err| {
+            let message = err.to_string();
+            // serde reports unknown/missing keys as "... field `name` ...", keep the key around for the diagnostics panel
+            let field = message
+                .split('`')
+                .nth(1)
+                .filter(|_| message.contains("field"))
+                .map(str::to_string);
+            ExtrasIssue::Parse { field, message }
+        })
+    }
+
+    /// Shape of every collider that isn't computed from the mesh
+    pub fn shape(&self, context: ShapeContext) -> Result<ExtrasShape, ExtrasIssue> {
+        if let BCollider::Compound = self.collider {
+            return compound_shape(&self.compound, context.scale).map(ExtrasShape::Compound);
+        }
+        let (shape, center) = primitive_shape(&self.collider, self.cube_size, self.radius, self.height, context)?;
+        Ok(ExtrasShape::Primitive { shape, center })
+    }
+}
+
+impl BCollider {
+    /// True for the shapes computed from the mesh itself rather than from sizes
+    pub fn is_mesh_based(&self) -> bool {
+        matches!(self, BCollider::TrimeshFromMesh | BCollider::ConvexHull | BCollider::ConvexDecompositionFromMesh)
+    }
+}
+
+/// Bounds of a mesh in its node's local space
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MeshBounds {
+    pub center: [f32; 3],
+    pub half_extents: [f32; 3],
+}
+
+impl MeshBounds {
+    pub fn from_min_max(min: [f32; 3], max: [f32; 3]) -> Self {
+        Self {
+            center: [0, 1, 2].map(|axis| (min[axis] + max[axis]) / 2.0),
+            half_extents: [0, 1, 2].map(|axis| (max[axis] - min[axis]) / 2.0),
+        }
+    }
+}
+
+/// What a node's collider is measured against
+/// - `scale` is the node's scale within its scene, avian3d applies it on top of the collider so sizes are divided by it
+/// - `bounds` are the mesh bounds in local space, used for any size the extras leave out
+#[derive(Clone, Copy, Debug)]
+pub struct ShapeContext {
+    pub scale: [f32; 3],
+    pub bounds: Option<MeshBounds>,
+}
+
+impl Default for ShapeContext {
+    fn default() -> Self {
+        Self { scale: [1.0; 3], bounds: None }
+    }
+}
+
+/// Primitive collider with its sizes in the node's local space
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PrimitiveShape {
+    /// Full size along each axis
+    Cuboid { size: [f32; 3] },
+    Sphere { radius: f32 },
+    Cylinder { radius: f32, height: f32 },
+    /// `length` only covers the cylindrical part between both hemispheres
+    Capsule { radius: f32, length: f32 },
+    Cone { radius: f32, height: f32 },
+}
+
+impl PrimitiveShape {
+    /// Full size of the shape's bounding box along each axis
+    pub fn size(&self) -> [f32; 3] {
+        match *self {
+            PrimitiveShape::Cuboid { size } => size,
+            PrimitiveShape::Sphere { radius } => [radius * 2.0; 3],
+            PrimitiveShape::Cylinder { radius, height } | PrimitiveShape::Cone { radius, height } => [radius * 2.0, height, radius * 2.0],
+            PrimitiveShape::Capsule { radius, length } => [radius * 2.0, length + radius * 2.0, radius * 2.0],
+        }
+    }
+}
+
+/// A part of a compound shape, placed in the node's local space
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CompoundPartShape {
+    pub translation: [f32; 3],
+    /// Euler XYZ, degrees
+    pub rotation: [f32; 3],
+    pub shape: PrimitiveShape,
+}
+
+/// Collider described by the extras, in the node's local space
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExtrasShape {
+    /// `center` is non-zero when the shape is derived from mesh bounds that aren't centred on the node origin
+    Primitive { shape: PrimitiveShape, center: [f32; 3] },
+    Compound(Vec<CompoundPartShape>),
+}
+
+impl ExtrasShape {
+    /// Full size of the shape's bounding box along each axis, rotated compound parts included
+    pub fn size(&self) -> [f32; 3] {
+        match self {
+            ExtrasShape::Primitive { shape, .. } => shape.size(),
+            ExtrasShape::Compound(parts) => {
+                let mut min = [f32::MAX; 3];
+                let mut max = [f32::MIN; 3];
+                for part in parts {
+                    let half = part.shape.size().map(|size| size / 2.0);
+                    for corner in 0..8 {
+                        let point = [0, 1, 2].map(|axis| if corner & (1 << axis) == 0 { -half[axis] } else { half[axis] });
+                        let point = rotate_xyz(point, part.rotation);
+                        for axis in 0..3 {
+                            min[axis] = min[axis].min(point[axis] + part.translation[axis]);
+                            max[axis] = max[axis].max(point[axis] + part.translation[axis]);
+                        }
+                    }
+                }
+                [0, 1, 2].map(|axis| (max[axis] - min[axis]).max(0.0))
+            }
+        }
+    }
+}
+
+/// Rotates a point by Euler XYZ angles in degrees, like `Quat::from_euler(EulerRot::XYZ, ..)` does
+fn rotate_xyz(point: [f32; 3], degrees: [f32; 3]) -> [f32; 3] {
+    let [x, y, z] = degrees.map(f32::to_radians);
+    // Intrinsic XYZ, so Z is applied first
+    let [px, py, pz] = point;
+    let (px, py) = (px * z.cos() - py * z.sin(), px * z.sin() + py * z.cos());
+    let (pz, px) = (pz * y.cos() - px * y.sin(), pz * y.sin() + px * y.cos());
+    let (py, pz) = (py * x.cos() - pz * x.sin(), py * x.sin() + pz * x.cos());
+    [px, py, pz]
+}
+
+fn max_element(vector: [f32; 3]) -> f32 {
+    vector[0].max(vector[1]).max(vector[2])
+}
+
+/// Resolves a primitive collider from the scene-space sizes given in the extras
+/// - Scene-space sizes are world-space for an instance spawned at scale 1, and grow with the instance scale
+/// - `cube_size` is the full size and `height` the full height, as shown in Blender's dimensions
+/// - Missing sizes are derived from the mesh bounds, or reported when the node has no mesh bounds
+/// - Returns the shape and where its center sits in the node's local space
+pub fn primitive_shape(kind: &BCollider, cube_size: Option<[f32; 3]>, radius: Option<f32>, height: Option<f32>, context: ShapeContext) -> Result<(PrimitiveShape, [f32; 3]), ExtrasIssue> {
+    let collider = match kind {
+        BCollider::Cuboid => "Cuboid",
+        BCollider::Sphere => "Sphere",
+        BCollider::Cylinder => "Cylinder",
+        BCollider::Capsule => "Capsule",
+        BCollider::Cone => "Cone",
+        other => {
+            return Err(ExtrasIssue::InvalidCompound { message: format!("{:?} is not a primitive collider", other) });
+        }
+    };
+    let missing = |field| ExtrasIssue::MissingField { field, collider };
+    let scale = context.scale;
+    let half_extents = context.bounds.map(|bounds| bounds.half_extents);
+    // Sizes in the extras are scene-space, the mesh bounds are already in the node's local space
+    let radial_scale = scale[0].max(scale[2]);
+    let local_radius = || {
+        radius
+            .map(|radius| radius / radial_scale)
+            .or_else(|| half_extents.map(|half| half[0].max(half[2])))
+            .ok_or_else(|| missing("radius"))
+    };
+    let local_height = || {
+        height
+            .map(|height| height / scale[1])
+            .or_else(|| half_extents.map(|half| half[1] * 2.0))
+            .ok_or_else(|| missing("height"))
+    };
+    let shape = match kind {
+        BCollider::Cuboid => {
+            let size = cube_size
+                .map(|size| [0, 1, 2].map(|axis| size[axis] / scale[axis]))
+                .or_else(|| half_extents.map(|half| half.map(|half| half * 2.0)))
+                .ok_or_else(|| missing("cube_size"))?;
+            PrimitiveShape::Cuboid { size }
+        }
+        BCollider::Sphere => {
+            let radius = radius
+                .map(|radius| radius / max_element(scale))
+                .or_else(|| half_extents.map(max_element))
+                .ok_or_else(|| missing("radius"))?;
+            PrimitiveShape::Sphere { radius }
+        }
+        BCollider::Cylinder => PrimitiveShape::Cylinder { radius: local_radius()?, height: local_height()? },
+        BCollider::Capsule => {
+            let radius = local_radius()?;
+            PrimitiveShape::Capsule { radius, length: (local_height()? - radius * 2.0).max(0.0) }
+        }
+        _ => PrimitiveShape::Cone { radius: local_radius()?, height: local_height()? },
+    };
+    let derived = match kind {
+        BCollider::Cuboid => cube_size.is_none(),
+        BCollider::Sphere => radius.is_none(),
+        _ => radius.is_none() || height.is_none(),
+    };
+    // Mesh bounds that aren't centred on the node origin need the derived shape moved onto them
+    let center = match context.bounds {
+        Some(bounds) if derived => bounds.center,
+        _ => [0.0; 3],
+    };
+    Ok((shape, center))
+}
+
+/// Resolves every part of a `Compound` collider
+/// - Parts don't use the mesh bounds, each of them must give its own sizes
+fn compound_shape(parts: &[BCompoundPart], scale: [f32; 3]) -> Result<Vec<CompoundPartShape>, ExtrasIssue> {
+    if parts.is_empty() {
+        return Err(ExtrasIssue::InvalidCompound { message: "Compound collider has no parts".to_string() });
+    }
+    let context = ShapeContext { scale, bounds: None };
+    parts
+        .iter()
+        .map(|part| {
+            let (shape, _) = primitive_shape(&part.collider, part.cube_size, part.radius, part.height, context)?;
+            Ok(CompoundPartShape {
+                translation: [0, 1, 2].map(|axis| part.translation[axis] / scale[axis]),
+                rotation: part.rotation,
+                shape,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_valid_extras() {
+        let extras = BMeshExtras::parse(r#"{"collider": "Cuboid", "rigid_body": "Dynamic", "cube_size": [1.0, 2.0, 3.0], "friction": 0.5}"#).unwrap();
+        assert!(matches!(extras.collider, BCollider::Cuboid));
+        assert_eq!(extras.cube_size, Some([1.0, 2.0, 3.0]));
+        assert_eq!(extras.friction, Some(0.5));
+        assert!(extras.joints.is_empty());
+    }
+
+    #[test]
+    fn rejects_misspelled_fields() {
+        let issue = BMeshExtras::parse(r#"{"collider": "Sphere", "rigid_body": "Dynamic", "raduis": 1.0}"#).unwrap_err();
+        assert!(matches!(issue, ExtrasIssue::Parse { field: Some(ref field), .. } if field == "raduis"), "{:?}", issue);
+    }
+
+    #[test]
+    fn rejects_missing_required_fields() {
+        let issue = BMeshExtras::parse(r#"{"collider": "Sphere"}"#).unwrap_err();
+        assert!(matches!(issue, ExtrasIssue::Parse { field: Some(ref field), .. } if field == "rigid_body"), "{:?}", issue);
+    }
+
+    #[test]
+    fn rejects_unknown_collider_kinds() {
+        let issue = BMeshExtras::parse(r#"{"collider": "Cube", "rigid_body": "Dynamic"}"#).unwrap_err();
+        assert!(matches!(issue, ExtrasIssue::Parse { .. }), "{:?}", issue);
+    }
+
+    fn missing_field(kind: &BCollider, radius: Option<f32>, height: Option<f32>) -> Option<&'static str> {
+        match primitive_shape(kind, None, radius, height, ShapeContext::default()) {
+            Err(ExtrasIssue::MissingField { field, .. }) => Some(field),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn primitives_without_mesh_bounds_need_their_sizes() {
+        assert_eq!(missing_field(&BCollider::Cuboid, None, None), Some("cube_size"));
+        assert_eq!(missing_field(&BCollider::Sphere, None, None), Some("radius"));
+        assert_eq!(missing_field(&BCollider::Sphere, Some(1.0), None), None);
+        for kind in [BCollider::Cylinder, BCollider::Capsule, BCollider::Cone] {
+            assert_eq!(missing_field(&kind, None, Some(2.0)), Some("radius"));
+            assert_eq!(missing_field(&kind, Some(1.0), None), Some("height"));
+            assert_eq!(missing_field(&kind, Some(1.0), Some(2.0)), None);
+        }
+    }
+
+    #[test]
+    fn primitive_sizes_are_divided_by_the_node_scale() {
+        let context = ShapeContext { scale: [2.0, 4.0, 2.0], bounds: None };
+        let (shape, center) = primitive_shape(&BCollider::Cuboid, Some([2.0, 4.0, 6.0]), None, None, context).unwrap();
+        assert_eq!(shape, PrimitiveShape::Cuboid { size: [1.0, 1.0, 3.0] });
+        assert_eq!(center, [0.0; 3]);
+        // The capsule length leaves out both caps
+        let (shape, _) = primitive_shape(&BCollider::Capsule, None, Some(1.0), Some(8.0), context).unwrap();
+        assert_eq!(shape, PrimitiveShape::Capsule { radius: 0.5, length: 1.0 });
+    }
+
+    #[test]
+    fn missing_sizes_are_derived_from_the_mesh_bounds() {
+        let bounds = MeshBounds::from_min_max([0.0, 0.0, 0.0], [2.0, 4.0, 1.0]);
+        let context = ShapeContext { scale: [1.0; 3], bounds: Some(bounds) };
+        let (shape, center) = primitive_shape(&BCollider::Cuboid, None, None, None, context).unwrap();
+        assert_eq!(shape, PrimitiveShape::Cuboid { size: [2.0, 4.0, 1.0] });
+        // The derived shape is moved onto the off-centre bounds
+        assert_eq!(center, [1.0, 2.0, 0.5]);
+        let (shape, center) = primitive_shape(&BCollider::Cylinder, None, Some(0.5), Some(3.0), context).unwrap();
+        assert_eq!(shape, PrimitiveShape::Cylinder { radius: 0.5, height: 3.0 });
+        assert_eq!(center, [0.0; 3]);
+    }
+
+    #[test]
+    fn mesh_colliders_are_not_primitives() {
+        let issue = primitive_shape(&BCollider::ConvexHull, None, Some(1.0), None, ShapeContext::default()).unwrap_err();
+        assert!(matches!(issue, ExtrasIssue::InvalidCompound { .. }), "{:?}", issue);
+    }
+
+    #[test]
+    fn compound_parts_need_primitive_colliders() {
+        let extras = |compound: &str| {
+            BMeshExtras::parse(&format!(r#"{{"collider": "Compound", "rigid_body": "Dynamic", "compound": {}}}"#, compound)).unwrap()
+        };
+        let issue = extras("[]").shape(ShapeContext::default()).unwrap_err();
+        assert!(matches!(issue, ExtrasIssue::InvalidCompound { .. }), "{:?}", issue);
+        let issue = extras(r#"[{"collider": "TrimeshFromMesh"}]"#).shape(ShapeContext::default()).unwrap_err();
+        assert!(matches!(issue, ExtrasIssue::InvalidCompound { .. }), "{:?}", issue);
+        let issue = extras(r#"[{"collider": "Sphere"}]"#).shape(ShapeContext::default()).unwrap_err();
+        assert_eq!(issue, ExtrasIssue::MissingField { field: "radius", collider: "Sphere" });
+
+        let context = ShapeContext { scale: [2.0; 3], bounds: None };
+        let shape = extras(r#"[{"collider": "Sphere", "radius": 1.0, "translation": [0.0, 4.0, 0.0]}]"#).shape(context).unwrap();
+        let ExtrasShape::Compound(parts) = shape else {
+            panic!("expected a compound shape");
+        };
+        assert_eq!(parts[0].translation, [0.0, 2.0, 0.0]);
+        assert_eq!(parts[0].shape, PrimitiveShape::Sphere { radius: 0.5 });
+    }
+}
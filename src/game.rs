@@ -5,7 +5,7 @@ use bevy::{ color, prelude::* };
 use bevy_asset::{AssetServer};
 use bevy_egui::{EguiPlugin, EguiPrimaryContextPass, PrimaryEguiContext};
 
-use crate::{SimulationState, interactions::{interactive_menu::*, *}};
+use crate::{SimulationState, entity_pipeline::{collider_cache_panel, extras_diagnostics_panel}, inspector::{inspector_panel, select_body}, scene_catalog::SceneCatalog, spawner::{draw_spawn_preview, spawn_panel, track_spawn_target}, transform_gizmo::*, interactions::{interactive_menu::*, *}};
 
 use super::GameState;
 
@@ -24,10 +24,18 @@ pub fn game_plugin(
         ))
         .init_resource::<SavedCameraTransforms>()
         .insert_resource(ImpulseSettings::default())
+        .init_resource::<BlastOcclusionRays>()
+        .insert_resource(GrabSettings::default())
+        .insert_resource(FieldSettings::default())
+        .insert_resource(WreckingBallSettings::default())
+        .insert_resource(WreckerSettings::default())
+        .insert_resource(LauncherSettings::default())
+        .insert_resource(PinSettings::default())
+        .insert_resource(CursorSnap::default())
         .insert_resource(CameraOrientation::default())
         .insert_resource(CursorDistance(10.0)) // set cursor distance on spawn
         .insert_resource(InteractionMode(InteractionModeType::Click))
-        .add_systems(EguiPrimaryContextPass, interactive_menu.run_if(in_state(GameState::Game)))
+        .add_systems(EguiPrimaryContextPass, (interactive_menu, extras_diagnostics_panel, collider_cache_panel, inspector_panel, transform_gizmo_panel, spawn_panel).run_if(in_state(GameState::Game)))
         .add_systems(Update, (
             // spawn_cubes.run_if(on_timer(Duration::from_secs(1))),
             keyboard_movement,
@@ -35,6 +43,8 @@ pub fn game_plugin(
             // Camera Zoom/Scroll runs only in Click Mode
             (
                 mouse_scroll,
+                (begin_transform_drag, select_body.run_if(not(is_dragging_gizmo)), drag_transform).chain(),
+                draw_transform_gizmo,
                 set_impulse_cursor_visibility::<false>,
                 set_wrecker_cursor_visibility::<false>,
             ).run_if(resource_equals(InteractionMode(InteractionModeType::Click))),
@@ -43,6 +53,7 @@ pub fn game_plugin(
                 scroll_control, // System to update cursor distance
                 draw_impulse_cursor,                // System to draw the gizmo
                 apply_force,
+                draw_blast_occlusion,
                 set_impulse_cursor_visibility::<true>,
                 set_wrecker_cursor_visibility::<false>,
             ).run_if(resource_equals(InteractionMode(InteractionModeType::Impulse))),
@@ -52,10 +63,46 @@ pub fn game_plugin(
                 set_impulse_cursor_visibility::<false>,
                 set_wrecker_cursor_visibility::<true>,
             ).run_if(resource_equals(InteractionMode(InteractionModeType::Wrecker))),
+            (
+                scroll_control,
+                (grab_body, drag_grab_anchor).chain(),
+                set_impulse_cursor_visibility::<false>,
+                set_wrecker_cursor_visibility::<false>,
+            ).run_if(resource_equals(InteractionMode(InteractionModeType::Grab))),
+            (
+                scroll_control,
+                draw_impulse_cursor,
+                apply_field,
+                set_impulse_cursor_visibility::<true>,
+                set_wrecker_cursor_visibility::<false>,
+            ).run_if(resource_equals(InteractionMode(InteractionModeType::Field))),
+            (
+                scroll_control,
+                draw_wrecker_cursor,
+                (spawn_wrecking_ball, update_wrecking_ball, draw_wrecking_ball_chain).chain(),
+                set_impulse_cursor_visibility::<false>,
+                set_wrecker_cursor_visibility::<true>,
+            ).run_if(resource_equals(InteractionMode(InteractionModeType::WreckingBall))),
+            (
+                draw_launch_trajectory,
+                fire_projectile,
+                set_impulse_cursor_visibility::<false>,
+                set_wrecker_cursor_visibility::<false>,
+            ).run_if(resource_equals(InteractionMode(InteractionModeType::Launcher))),
+            (
+                pin_body.run_if(not(is_dragging_gizmo)),
+                set_impulse_cursor_visibility::<false>,
+                set_wrecker_cursor_visibility::<false>,
+            ).run_if(resource_equals(InteractionMode(InteractionModeType::Pin))),
+            despawn_wrecking_ball.run_if(not(resource_equals(InteractionMode(InteractionModeType::WreckingBall)))),
+            release_grab,
+            release_transform_drag,
+            (track_spawn_target, draw_spawn_preview).chain(),
+            draw_pinned_bodies,
             toggle_debug_render_state,
             game_action,
         ).run_if(in_state(GameState::Game).and(not(in_state(SimulationState::Paused)))))
-        .add_systems(OnExit(GameState::Game), cleanup_game);
+        .add_systems(OnExit(GameState::Game), (cleanup_game, despawn_wrecking_ball, despawn_projectiles));
 }
 
 /// Tag used on all entities located in the `GameState::Game`
@@ -65,6 +112,7 @@ struct OnGameScreen;
 fn game_setup(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    catalog: Res<SceneCatalog>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
 ) {
@@ -79,15 +127,19 @@ fn game_setup(
         OnGameScreen,
     ));
 
+    // Needs the asset storages, so it's created here rather than when the plugin is built
+    commands.init_resource::<ProjectileAssets>();
+
     let impulse_ball = commands.spawn((
-        SceneRoot(
-            asset_server.load(GltfAssetLabel::Scene(5).from_asset("shapes.glb"))
-        ),
         Transform::from_xyz(0.0, 0.0, 0.0),
         Visibility::Hidden,
         OnGameScreen,
     ))
     .insert(ImpulseCursor).id();
+    // The cursor still works without its model, `scene` already warns about the missing scene
+    if let Some(target) = catalog.scene("target") {
+        commands.entity(impulse_ball).insert(SceneRoot(target));
+    }
 
     let sphere = meshes.add(Sphere::new(0.5));
 
@@ -97,11 +149,19 @@ fn game_setup(
         Transform::from_xyz(0.0, 10.0, 0.0),
         Collider::sphere(0.5),
         RigidBody::Kinematic,
+        SweptCcd::default(),
         Visibility::Hidden,
         OnGameScreen,
     ))
     .insert(WreckerCursor).id();
 
+    commands.spawn((
+        Transform::default(),
+        RigidBody::Kinematic,
+        GrabAnchor,
+        OnGameScreen,
+    ));
+
     let text_style = TextFont {
         font: asset_server.load(r"fonts\FiraMono-Medium.ttf"),
         ..Default::default()
@@ -0,0 +1,343 @@
+use std::collections::HashMap;
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy_egui::input::EguiWantsInput;
+
+use crate::{GameState, interactions::{WreckerCursor, interactive_menu::{EntityTag, Ground}}, scene_catalog::{SceneCatalog, SceneKind}, spawner::{SpawnVelocity, launch_spawned_scene}};
+
+/// Undo/redo of the authoring actions of the playground (spawning and deleting scenes, scaling the wrecker)
+/// - The interactive menu sends `HistoryAction`s instead of editing the playground itself
+/// - Only the edits are reverted, what physics did to the scenes in the meantime isn't
+/// - Edits are resolved against `EditHistory::spawned` rather than a query, so several actions in the same frame see each other's spawns and despawns
+pub fn history_plugin(
+    app: &mut App,
+) {
+    app
+        .init_resource::<EditHistory>()
+        .add_message::<HistoryAction>()
+        .add_systems(Update, (history_shortcuts, apply_history_actions).chain().run_if(in_state(GameState::Game)))
+        .add_systems(OnEnter(GameState::Menu), clear_history);
+}
+
+/// Playground edit requested from the menu, resolved into an `Edit` when it is applied
+#[derive(Debug, Clone)]
+pub enum EditRequest {
+    /// Spawns a catalog scene at each transform, despawning the playground scenes of the same kind first when `replace` is set
+    Spawn { kind: SceneKind, name: String, transforms: Vec<Transform>, velocity: SpawnVelocity, replace: bool },
+    /// Despawns every playground scene of a kind
+    DeleteKind(SceneKind),
+    ScaleWrecker(Vec3),
+}
+
+#[derive(Message)]
+pub enum HistoryAction {
+    Apply(EditRequest),
+    Undo,
+    Redo,
+}
+
+/// Scene spawned from the playground menu, with what is needed to spawn it again
+/// - The id stays the same across undo/redo, unlike the entity
+#[derive(Component, Debug, Clone)]
+pub struct PlaygroundScene {
+    id: u64,
+    kind: SceneKind,
+    name: String,
+    transform: Transform,
+    velocity: SpawnVelocity,
+}
+
+/// An applied playground edit, holding everything needed to revert and reapply it
+#[derive(Debug, Clone)]
+pub enum Edit {
+    Spawn { scenes: Vec<PlaygroundScene>, replaced: Vec<PlaygroundScene> },
+    /// Undoing respawns the scenes where they were first spawned, not where physics had moved them when deleted
+    Delete(Vec<PlaygroundScene>),
+    ScaleWrecker { from: Vec3, to: Vec3 },
+}
+
+impl Edit {
+    pub fn label(&self) -> String {
+        match self {
+            Edit::Spawn { scenes, .. } => match scenes.as_slice() {
+                [scene] => format!("Spawn {:?} '{}'", scene.kind, scene.name),
+                [scene, ..] => format!("Spawn {} {:?}s '{}'", scenes.len(), scene.kind, scene.name),
+                [] => "Spawn nothing".to_string(),
+            },
+            Edit::Delete(scenes) => format!("Delete {} scene(s), undo respawns them as spawned", scenes.len()),
+            Edit::ScaleWrecker { from, to } => format!("Scale Wrecker {:.1} -> {:.1}", from.x, to.x),
+        }
+    }
+}
+
+/// Applied edits, most recent last, and the undone ones that can still be redone
+#[derive(Resource, Default)]
+pub struct EditHistory {
+    done: Vec<Edit>,
+    undone: Vec<Edit>,
+    /// Id of the next `PlaygroundScene`
+    next_id: u64,
+    /// Root entity of every spawned playground scene by id, updated as soon as the spawn/despawn commands are queued
+    spawned: HashMap<u64, (Entity, PlaygroundScene)>,
+}
+
+impl EditHistory {
+    pub fn done(&self) -> &[Edit] {
+        &self.done
+    }
+
+    pub fn undone(&self) -> &[Edit] {
+        &self.undone
+    }
+
+    fn new_scene(&mut self, kind: SceneKind, name: String, transform: Transform, velocity: SpawnVelocity) -> PlaygroundScene {
+        self.next_id += 1;
+        PlaygroundScene { id: self.next_id, kind, name, transform, velocity }
+    }
+
+    fn scenes_of_kind(&self, kind: SceneKind) -> Vec<PlaygroundScene> {
+        self.spawned.values().filter(|(_, scene)| scene.kind == kind).map(|(_, scene)| scene.clone()).collect()
+    }
+
+    /// Adds an applied edit, a new edit starts a new branch so the undone edits can't be redone anymore
+    fn record(&mut self, edit: Edit) {
+        self.done.push(edit);
+        self.undone.clear();
+    }
+
+    /// Moves the most recent edit to the undone ones, returning it so it can be reverted
+    fn undo(&mut self) -> Option<Edit> {
+        let edit = self.done.pop()?;
+        self.undone.push(edit.clone());
+        Some(edit)
+    }
+
+    /// Moves the last undone edit back to the applied ones, returning it so it can be reapplied
+    fn redo(&mut self) -> Option<Edit> {
+        let edit = self.undone.pop()?;
+        self.done.push(edit.clone());
+        Some(edit)
+    }
+}
+
+/// What the menu needs to send edits and show the history
+#[derive(SystemParam)]
+pub struct PlaygroundHistory<'w> {
+    history: Res<'w, EditHistory>,
+    actions: MessageWriter<'w, HistoryAction>,
+}
+
+impl PlaygroundHistory<'_> {
+    pub fn apply(&mut self, request: EditRequest) {
+        self.actions.write(HistoryAction::Apply(request));
+    }
+
+    pub fn undo(&mut self) {
+        self.actions.write(HistoryAction::Undo);
+    }
+
+    pub fn redo(&mut self) {
+        self.actions.write(HistoryAction::Redo);
+    }
+
+    pub fn history(&self) -> &EditHistory {
+        &self.history
+    }
+}
+
+/// Ctrl+Z to undo, Ctrl+Y or Ctrl+Shift+Z to redo, unless egui is using the keyboard
+fn history_shortcuts(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    egui_input: Res<EguiWantsInput>,
+    mut actions: MessageWriter<HistoryAction>,
+) {
+    if egui_input.wants_any_keyboard_input() || !keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
+        return;
+    }
+    let shift = keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    if keyboard_input.just_pressed(KeyCode::KeyY) || (shift && keyboard_input.just_pressed(KeyCode::KeyZ)) {
+        actions.write(HistoryAction::Redo);
+    } else if keyboard_input.just_pressed(KeyCode::KeyZ) {
+        actions.write(HistoryAction::Undo);
+    }
+}
+
+/// Applies, undoes and redoes the edits sent by the menu and the shortcuts
+fn apply_history_actions(
+    mut commands: Commands,
+    mut actions: MessageReader<HistoryAction>,
+    mut history: ResMut<EditHistory>,
+    catalog: Res<SceneCatalog>,
+    mut wrecker_query: Query<&mut Transform, With<WreckerCursor>>,
+) {
+    for action in actions.read() {
+        match action {
+            HistoryAction::Apply(request) => {
+                let edit = match request.clone() {
+                    EditRequest::Spawn { kind, name, transforms, velocity, replace } => Edit::Spawn {
+                        scenes: transforms
+                            .into_iter()
+                            .map(|transform| history.new_scene(kind, name.clone(), transform, velocity))
+                            .collect(),
+                        replaced: if replace { history.scenes_of_kind(kind) } else { Vec::new() },
+                    },
+                    EditRequest::DeleteKind(kind) => Edit::Delete(history.scenes_of_kind(kind)),
+                    EditRequest::ScaleWrecker(to) => {
+                        let Some(from) = wrecker_query.iter().next().map(|transform| transform.scale) else {
+                            continue;
+                        };
+                        Edit::ScaleWrecker { from, to }
+                    }
+                };
+                redo(&mut commands, &catalog, &mut history.spawned, &mut wrecker_query, &edit);
+                history.record(edit);
+            }
+            HistoryAction::Undo => {
+                if let Some(edit) = history.undo() {
+                    undo(&mut commands, &catalog, &mut history.spawned, &mut wrecker_query, &edit);
+                }
+            }
+            HistoryAction::Redo => {
+                if let Some(edit) = history.redo() {
+                    redo(&mut commands, &catalog, &mut history.spawned, &mut wrecker_query, &edit);
+                }
+            }
+        }
+    }
+}
+
+fn redo(
+    commands: &mut Commands,
+    catalog: &SceneCatalog,
+    spawned: &mut HashMap<u64, (Entity, PlaygroundScene)>,
+    wrecker_query: &mut Query<&mut Transform, With<WreckerCursor>>,
+    edit: &Edit,
+) {
+    match edit {
+        Edit::Spawn { scenes, replaced } => {
+            despawn_scenes(commands, spawned, replaced);
+            for scene in scenes {
+                spawn_scene(commands, catalog, spawned, scene);
+            }
+        }
+        Edit::Delete(deleted) => despawn_scenes(commands, spawned, deleted),
+        Edit::ScaleWrecker { to, .. } => set_wrecker_scale(wrecker_query, *to),
+    }
+}
+
+fn undo(
+    commands: &mut Commands,
+    catalog: &SceneCatalog,
+    spawned: &mut HashMap<u64, (Entity, PlaygroundScene)>,
+    wrecker_query: &mut Query<&mut Transform, With<WreckerCursor>>,
+    edit: &Edit,
+) {
+    match edit {
+        Edit::Spawn { scenes, replaced } => {
+            despawn_scenes(commands, spawned, scenes);
+            for scene in replaced {
+                spawn_scene(commands, catalog, spawned, scene);
+            }
+        }
+        Edit::Delete(deleted) => {
+            for scene in deleted {
+                spawn_scene(commands, catalog, spawned, scene);
+            }
+        }
+        Edit::ScaleWrecker { from, .. } => set_wrecker_scale(wrecker_query, *from),
+    }
+}
+
+fn spawn_scene(commands: &mut Commands, catalog: &SceneCatalog, spawned: &mut HashMap<u64, (Entity, PlaygroundScene)>, scene: &PlaygroundScene) {
+    let Some(mut entity) = catalog.spawn(commands, &scene.name, (scene.transform, scene.clone(), EntityTag)) else {
+        return;
+    };
+    if scene.kind == SceneKind::Map {
+        entity.insert(Ground);
+    }
+    if scene.velocity != SpawnVelocity::default() {
+        entity.insert(scene.velocity).observe(launch_spawned_scene);
+    }
+    spawned.insert(scene.id, (entity.id(), scene.clone()));
+}
+
+fn despawn_scenes(commands: &mut Commands, spawned: &mut HashMap<u64, (Entity, PlaygroundScene)>, despawned: &[PlaygroundScene]) {
+    for scene in despawned {
+        if let Some((entity, _)) = spawned.remove(&scene.id) {
+            // The scene may already be gone with the rest of the playground
+            commands.entity(entity).try_despawn();
+        }
+    }
+}
+
+fn set_wrecker_scale(wrecker_query: &mut Query<&mut Transform, With<WreckerCursor>>, scale: Vec3) {
+    for mut transform in wrecker_query.iter_mut() {
+        transform.scale = scale;
+    }
+}
+
+fn clear_history(
+    mut history: ResMut<EditHistory>,
+) {
+    history.done.clear();
+    history.undone.clear();
+    history.spawned.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scale(to: f32) -> Edit {
+        Edit::ScaleWrecker { from: Vec3::ONE, to: Vec3::splat(to) }
+    }
+
+    fn labels(edits: &[Edit]) -> Vec<String> {
+        edits.iter().map(Edit::label).collect()
+    }
+
+    #[test]
+    fn undo_and_redo_move_edits_between_the_stacks() {
+        let mut history = EditHistory::default();
+        history.record(scale(2.0));
+        history.record(scale(3.0));
+
+        assert_eq!(history.undo().map(|edit| edit.label()), Some(scale(3.0).label()));
+        assert_eq!(labels(history.done()), labels(&[scale(2.0)]));
+        assert_eq!(labels(history.undone()), labels(&[scale(3.0)]));
+
+        assert_eq!(history.redo().map(|edit| edit.label()), Some(scale(3.0).label()));
+        assert_eq!(labels(history.done()), labels(&[scale(2.0), scale(3.0)]));
+        assert!(history.undone().is_empty());
+    }
+
+    #[test]
+    fn undo_and_redo_do_nothing_on_empty_stacks() {
+        let mut history = EditHistory::default();
+        assert!(history.undo().is_none());
+        assert!(history.redo().is_none());
+        history.record(scale(2.0));
+        assert!(history.redo().is_none());
+        assert_eq!(history.done().len(), 1);
+    }
+
+    #[test]
+    fn recording_an_edit_drops_the_undone_ones() {
+        let mut history = EditHistory::default();
+        history.record(scale(2.0));
+        history.record(scale(3.0));
+        history.undo();
+        history.undo();
+        history.record(scale(4.0));
+        assert_eq!(labels(history.done()), labels(&[scale(4.0)]));
+        assert!(history.undone().is_empty());
+    }
+
+    #[test]
+    fn scene_ids_are_unique_per_history() {
+        let mut history = EditHistory::default();
+        let first = history.new_scene(SceneKind::Shape, "Cube".to_string(), Transform::default(), default());
+        let second = history.new_scene(SceneKind::Shape, "Cube".to_string(), Transform::default(), default());
+        assert_ne!(first.id, second.id);
+    }
+}
@@ -0,0 +1,273 @@
+use avian3d::prelude::*;
+use bevy::{color, ecs::{query::QueryData, system::SystemParam}, prelude::*};
+use bevy_egui::{EguiContexts, egui};
+
+use crate::{entity_pipeline::StructureBlock, interactions::{Pinned, SceneCursor, freeze, unpin}, scene_catalog::SceneKind, transform_gizmo::TransformGizmo};
+
+/// Selecting rigid bodies in Click mode and inspecting them in an egui panel
+/// - `select_body` and `inspector_panel` are added by the scenes that support selection (e.g. the playground)
+pub fn inspector_plugin(
+    app: &mut App,
+) {
+    app
+        .init_resource::<Selection>()
+        .add_systems(Update, (validate_selection, highlight_selection).chain());
+}
+
+/// How far the selection ray reaches
+const SELECT_REACH: f32 = 500.0;
+/// How far a duplicated body is placed above the original
+const DUPLICATE_OFFSET: f32 = 1.0;
+
+/// The rigid body picked in Click mode, shown in the inspector
+#[derive(Resource, Default)]
+pub struct Selection(pub Option<Entity>);
+
+/// Collider of the selected body with its editable material
+#[derive(QueryData)]
+#[query_data(mutable)]
+pub struct InspectedCollider {
+    entity: Entity,
+    collider_of: &'static ColliderOf,
+    collider: &'static Collider,
+    friction: Option<&'static mut Friction>,
+    restitution: Option<&'static mut Restitution>,
+}
+
+/// Kind of the catalog scene an entity belongs to
+#[derive(SystemParam)]
+pub struct SceneKinds<'w, 's> {
+    parents: Query<'w, 's, &'static ChildOf>,
+    kinds: Query<'w, 's, &'static SceneKind>,
+}
+
+impl SceneKinds<'_, '_> {
+    /// Scenes spawned through the catalog carry their kind on the scene root
+    fn of(&self, entity: Entity) -> Option<SceneKind> {
+        self.parents.iter_ancestors(entity).find_map(|ancestor| self.kinds.get(ancestor).ok()).copied()
+    }
+}
+
+/// Everything the inspector shows and edits on the selected body
+#[derive(QueryData)]
+#[query_data(mutable)]
+pub struct InspectedBody {
+    entity: Entity,
+    name: Option<&'static Name>,
+    rigid_body: &'static RigidBody,
+    locked_axes: Option<&'static LockedAxes>,
+    pinned: Option<&'static Pinned>,
+    mass: &'static ComputedMass,
+    center_of_mass: &'static ComputedCenterOfMass,
+    linear_velocity: &'static mut LinearVelocity,
+    angular_velocity: &'static mut AngularVelocity,
+    sleeping: Has<Sleeping>,
+    structure_block: Has<StructureBlock>,
+}
+
+/// Selects the rigid body under the cursor on click, clicking on nothing clears the selection
+pub fn select_body(
+    cursor: SceneCursor,
+    colliders: Query<&ColliderOf>,
+    bodies: Query<(), With<RigidBody>>,
+    mut selection: ResMut<Selection>,
+) {
+    if !cursor.just_clicked() {
+        return;
+    }
+    selection.0 = cursor.hit(SELECT_REACH).and_then(|(_, hit)| {
+        // The hit collider can be a child of the body it belongs to
+        let body = colliders.get(hit.entity).map_or(hit.entity, |collider_of| collider_of.body);
+        bodies.contains(body).then_some(body)
+    });
+}
+
+/// Clears the selection once its body is despawned (e.g. by a scene reset)
+fn validate_selection(
+    mut selection: ResMut<Selection>,
+    bodies: Query<(), With<RigidBody>>,
+) {
+    if selection.0.is_some_and(|entity| !bodies.contains(entity)) {
+        selection.0 = None;
+    }
+}
+
+/// Outlines the colliders of the selected body
+fn highlight_selection(
+    selection: Res<Selection>,
+    colliders: Query<(&ColliderOf, &ColliderAabb)>,
+    mut gizmos: Gizmos,
+) {
+    let Some(selected) = selection.0 else {
+        return;
+    };
+    for (collider_of, aabb) in &colliders {
+        if collider_of.body == selected {
+            gizmos.cuboid(
+                Transform::from_translation(aabb.center()).with_scale(aabb.size()),
+                color::palettes::css::YELLOW,
+            );
+        }
+    }
+}
+
+/// Panel showing the selected body, values are edited live and applied straight to its components
+pub fn inspector_panel(
+    mut contexts: EguiContexts,
+    mut commands: Commands,
+    mut selection: ResMut<Selection>,
+    mut bodies: Query<InspectedBody>,
+    mut colliders: Query<InspectedCollider>,
+    scene_kinds: SceneKinds,
+    gizmo: Res<TransformGizmo>,
+) -> Result {
+    let Some(mut body) = selection.0.and_then(|entity| bodies.get_mut(entity).ok()) else {
+        return Ok(());
+    };
+    let entity = body.entity;
+    let kind = scene_kinds.of(entity);
+
+    let mut open = true;
+    egui::Window::new("Inspector")
+        .open(&mut open)
+        .resizable(true)
+        .vscroll(true)
+        .default_open(true)
+        .show(contexts.ctx_mut()?, |ui| {
+            ui.label(format!("Entity: {}", entity));
+            ui.label(format!("Name: {}", body.name.map_or("(unnamed)", |name| name.as_str())));
+            let mut tags = Vec::new();
+            if let Some(kind) = kind {
+                tags.push(format!("{:?}", kind));
+            }
+            if body.structure_block {
+                tags.push("StructureBlock".to_string());
+            }
+            ui.label(format!("Tags: {}", if tags.is_empty() { "-".to_string() } else { tags.join(", ") }));
+            ui.label(format!("Rigid Body: {:?}", *body.rigid_body));
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.label("Mass:");
+                let mut mass = body.mass.value();
+                // Static bodies have an infinite mass
+                if !mass.is_finite() {
+                    ui.label("infinite");
+                } else if ui.add(egui::DragValue::new(&mut mass).speed(0.1).range(0.001..=f32::MAX)).changed() {
+                    commands.entity(entity).insert(Mass(mass));
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Center of Mass:");
+                let mut center_of_mass = body.center_of_mass.0;
+                if vec3_drag(ui, &mut center_of_mass, 0.01) {
+                    commands.entity(entity).insert(CenterOfMass(center_of_mass));
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Linear Velocity:");
+                vec3_drag(ui, &mut body.linear_velocity.0, 0.1);
+            });
+            ui.horizontal(|ui| {
+                ui.label("Angular Velocity:");
+                vec3_drag(ui, &mut body.angular_velocity.0, 0.1);
+            });
+            ui.horizontal(|ui| {
+                ui.label(format!("Sleeping: {}", body.sleeping));
+                if body.sleeping && ui.button("Wake Up").clicked() {
+                    commands.entity(entity).remove::<Sleeping>();
+                }
+            });
+
+            for collider in &mut colliders {
+                if collider.collider_of.body != entity {
+                    continue;
+                }
+                let (collider_entity, friction, restitution) = (collider.entity, collider.friction, collider.restitution);
+                ui.separator();
+                ui.label(format!("Collider: {:?}", collider.collider.shape().shape_type()));
+                // Colliders without a material use the avian3d defaults, editing one gives the collider its own component
+                ui.horizontal(|ui| {
+                    ui.label("Friction (dynamic/static):");
+                    let mut value = friction.as_deref().copied().unwrap_or_default();
+                    let dynamic = ui.add(egui::DragValue::new(&mut value.dynamic_coefficient).speed(0.01).range(0.0..=f32::MAX)).changed();
+                    let fixed = ui.add(egui::DragValue::new(&mut value.static_coefficient).speed(0.01).range(0.0..=f32::MAX)).changed();
+                    if dynamic || fixed {
+                        match friction {
+                            Some(mut friction) => *friction = value,
+                            None => {
+                                commands.entity(collider_entity).insert(value);
+                            }
+                        }
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Restitution:");
+                    let mut value = restitution.as_deref().copied().unwrap_or_default();
+                    if ui.add(egui::DragValue::new(&mut value.coefficient).speed(0.01).range(0.0..=1.0)).changed() {
+                        match restitution {
+                            Some(mut restitution) => *restitution = value,
+                            None => {
+                                commands.entity(collider_entity).insert(value);
+                            }
+                        }
+                    }
+                });
+            }
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                // Freezing is the same as pinning in Freeze mode, unpinning restores the type the body had before
+                // - Not while a gizmo drag has the body kinematic, the drag would give it its old type back on release
+                let dragging = gizmo.is_dragging();
+                if let Some(pinned) = body.pinned {
+                    if ui.add_enabled(!dragging, egui::Button::new(if body.rigid_body.is_static() { "Unfreeze" } else { "Unpin" })).clicked() {
+                        unpin(&mut commands, entity, pinned);
+                    }
+                } else if ui.add_enabled(!dragging && !body.rigid_body.is_static(), egui::Button::new("Freeze")).clicked() {
+                    freeze(&mut commands, entity, *body.rigid_body, body.locked_axes.copied());
+                }
+                if ui.button("Reset Velocity").clicked() {
+                    body.linear_velocity.0 = Vec3::ZERO;
+                    body.angular_velocity.0 = Vec3::ZERO;
+                }
+                if ui.button("Duplicate").clicked() {
+                    duplicate_body(&mut commands, entity, body.pinned);
+                }
+                if ui.button("Delete").clicked() {
+                    commands.entity(entity).despawn();
+                    selection.0 = None;
+                }
+            });
+        });
+    if !open {
+        selection.0 = None;
+    }
+    Ok(())
+}
+
+/// Three drag values for the axes of a vector, returns true when one was changed
+pub fn vec3_drag(ui: &mut egui::Ui, value: &mut Vec3, speed: f32) -> bool {
+    let x = ui.add(egui::DragValue::new(&mut value.x).speed(speed).prefix("x: ")).changed();
+    let y = ui.add(egui::DragValue::new(&mut value.y).speed(speed).prefix("y: ")).changed();
+    let z = ui.add(egui::DragValue::new(&mut value.z).speed(speed).prefix("z: ")).changed();
+    x || y || z
+}
+
+/// Spawns a copy of a body slightly above it, in the same scene
+/// - Only the rendering and physics setup is copied, the physics engine fills in the rest
+/// - The copy of a pinned or frozen body isn't pinned, it gets the type and locked axes the body had before
+fn duplicate_body(commands: &mut Commands, entity: Entity, pinned: Option<&Pinned>) {
+    let mut original = commands.entity(entity);
+    let mut copy = original.clone_and_spawn_with_opt_in(|builder| {
+        builder
+            .allow::<(Name, Transform, Visibility, Mesh3d, MeshMaterial3d<StandardMaterial>, ChildOf)>()
+            .allow::<(RigidBody, Collider, Mass, CenterOfMass, ColliderDensity, Friction, Restitution, Sensor, CollisionLayers)>()
+            .allow::<(LinearDamping, AngularDamping, GravityScale, LinearVelocity, AngularVelocity, DebugRender, StructureBlock)>();
+    });
+    copy.entry::<Transform>().and_modify(|mut transform| transform.translation.y += DUPLICATE_OFFSET);
+    let copy = copy.id();
+    if let Some(pinned) = pinned {
+        unpin(commands, copy, pinned);
+    }
+}
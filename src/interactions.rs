@@ -1,7 +1,7 @@
 use avian3d::prelude::*;
-use bevy::prelude::*;
+use bevy::{color, ecs::system::SystemParam, platform::collections::HashMap, prelude::*, scene::SceneInstanceReady};
 use bevy_egui::input::EguiWantsInput;
-use crate::{game::FlyCamera, levels::LevelsFlyCamera};
+use crate::{game::FlyCamera, levels::LevelsFlyCamera, scene_catalog::SceneCatalog};
 
 #[derive(Component)]
 pub struct ExampleLabel {
@@ -19,6 +19,11 @@ pub enum InteractionModeType {
     Click,
     Impulse,
     Wrecker,
+    Grab,
+    Field,
+    WreckingBall,
+    Launcher,
+    Pin,
 }
 
 #[derive(Resource)]
@@ -36,34 +41,472 @@ pub struct WreckerCursor;
 #[derive(Resource)]
 pub struct ImpulseSettings {
     pub blast_radius: f32,
-    pub max_force: f32
+    pub max_force: f32,
+    pub falloff: BlastFalloff,
+    pub direction: BlastDirection,
+    pub response: BlastResponse,
+    /// Raycast from the blast to each body, static geometry in the way scales the impulse by `occluded_force`
+    pub occlusion: bool,
+    /// Fraction of the impulse left for occluded bodies, 0 blocks it entirely
+    pub occluded_force: f32,
+    /// Draw the occluded rays of the last blast with gizmos
+    pub show_occlusion: bool,
 }
 impl Default for ImpulseSettings {
     fn default() -> Self {
-        Self { blast_radius: 50.0, max_force: 100.0 }
+        Self { blast_radius: 50.0, max_force: 100.0, falloff: BlastFalloff::Linear, direction: BlastDirection::Radial, response: BlastResponse::Impulse, occlusion: false, occluded_force: 0.0, show_occlusion: false }
+    }
+}
+
+/// Limits on how fast the `WreckerCursor` follows the cursor
+#[derive(Resource)]
+pub struct WreckerSettings {
+    pub max_speed: f32,
+    pub max_acceleration: f32,
+}
+impl Default for WreckerSettings {
+    fn default() -> Self {
+        Self { max_speed: 40.0, max_acceleration: 400.0 }
+    }
+}
+
+/// What the launcher fires and how, changes apply to the next shot
+#[derive(Resource)]
+pub struct LauncherSettings {
+    pub projectile: LauncherProjectile,
+    pub speed: f32,
+    pub mass: f32,
+    /// Uniform scale of the projectile, primitives are 1 unit wide at size 1
+    pub size: f32,
+    /// Shots per second while the left mouse button is held
+    pub fire_rate: f32,
+}
+impl Default for LauncherSettings {
+    fn default() -> Self {
+        Self { projectile: LauncherProjectile::Sphere, speed: 30.0, mass: 5.0, size: 1.0, fire_rate: 4.0 }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LauncherProjectile {
+    Sphere,
+    Cube,
+    /// A shape scene from the `SceneCatalog`, by name
+    Shape(String),
+}
+
+/// Tags everything fired by the launcher so it can be cleaned up
+#[derive(Component)]
+pub struct Projectile;
+
+/// Launch velocity and mass of a scene projectile, given to the scene's bodies once the pipeline has created them
+#[derive(Component)]
+struct SceneProjectileLaunch {
+    velocity: Vec3,
+    mass: f32,
+}
+
+/// Meshes and material shared by every primitive projectile
+#[derive(Resource)]
+pub struct ProjectileAssets {
+    sphere: Handle<Mesh>,
+    cube: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+}
+impl FromWorld for ProjectileAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mut meshes = world.resource_mut::<Assets<Mesh>>();
+        let sphere = meshes.add(Sphere::new(0.5));
+        let cube = meshes.add(Cuboid::from_length(1.0));
+        let material = world.resource_mut::<Assets<StandardMaterial>>().add(Color::srgb(0.9, 0.4, 0.1));
+        Self { sphere, cube, material }
+    }
+}
+
+/// Distance in front of the camera where projectiles are spawned, so they don't start inside of it
+const LAUNCH_OFFSET: f32 = 1.5;
+/// Time step and duration of the predicted trajectory, in seconds
+const TRAJECTORY_STEP: f32 = 0.05;
+const TRAJECTORY_DURATION: f32 = 3.0;
+
+/// How Pin mode holds the bodies it pins, changes apply to the next body pinned
+#[derive(Resource)]
+pub struct PinSettings {
+    pub mode: PinMode,
+    /// Axes locked by `PinMode::LockAxes`, in x/y/z order
+    pub translation: [bool; 3],
+    pub rotation: [bool; 3],
+}
+impl Default for PinSettings {
+    fn default() -> Self {
+        Self { mode: PinMode::Freeze, translation: [true; 3], rotation: [true; 3] }
+    }
+}
+
+impl PinSettings {
+    fn locked_axes(&self) -> LockedAxes {
+        // The bits go from the translation x axis (highest) to the rotation z axis (lowest)
+        let bits = self.translation.iter().chain(&self.rotation).fold(0, |bits, locked| bits << 1 | *locked as u8);
+        LockedAxes::from_bits(bits)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PinMode {
+    /// Turns the body static, nothing moves it anymore
+    Freeze,
+    /// Keeps the body dynamic but locks the axes picked in `PinSettings`
+    LockAxes,
+}
+
+/// Body pinned in Pin mode or frozen from the inspector, with what it had before so unpinning can give it back
+/// - Both go through `freeze`/`lock_axes` and `unpin`, so a body frozen in one can be unpinned from the other
+#[derive(Component)]
+pub struct Pinned {
+    rigid_body: RigidBody,
+    locked_axes: Option<LockedAxes>,
+}
+
+/// How far the cursor ray reaches to pin a body
+const PIN_REACH: f32 = 500.0;
+
+/// Heavy dynamic ball hanging from the `WreckerCursor`, which then acts as the kinematic anchor of its chain
+#[derive(Component)]
+pub struct WreckingBall;
+
+/// Joint chaining the `WreckingBall` to the `WreckerCursor`
+#[derive(Component)]
+pub struct WreckingBallChain;
+
+/// Chain and ball of the wrecking ball, changes apply to the ball that is already hanging
+#[derive(Resource)]
+pub struct WreckingBallSettings {
+    pub chain_length: f32,
+    pub ball_mass: f32,
+}
+impl Default for WreckingBallSettings {
+    fn default() -> Self {
+        Self { chain_length: 6.0, ball_mass: 50.0 }
+    }
+}
+
+const WRECKING_BALL_RADIUS: f32 = 1.0;
+/// Spacing of the links drawn along the chain
+const CHAIN_LINK_SPACING: f32 = 0.4;
+
+/// Continuous force applied around the cursor while the left mouse button is held in Field mode
+#[derive(Resource)]
+pub struct FieldSettings {
+    pub profile: FieldProfile,
+    /// Acceleration at the cursor point, fading linearly to 0 at the edge of the radius
+    pub strength: f32,
+    pub radius: f32,
+}
+impl Default for FieldSettings {
+    fn default() -> Self {
+        Self { profile: FieldProfile::Attractor, strength: 30.0, radius: 15.0 }
+    }
+}
+
+/// How the field pushes the bodies within its radius
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FieldProfile {
+    /// Pulls bodies towards the cursor point
+    Attractor,
+    /// Pushes bodies away from the cursor point
+    Repulsor,
+    /// Spins bodies around the camera axis going through the cursor point
+    Vortex,
+    /// Pushes bodies along the camera forward, only for bodies within `half_angle` (degrees) of it as seen from the cursor point
+    Wind { half_angle: f32 },
+}
+
+impl FieldProfile {
+    pub const ALL: [FieldProfile; 4] = [
+        FieldProfile::Attractor,
+        FieldProfile::Repulsor,
+        FieldProfile::Vortex,
+        FieldProfile::Wind { half_angle: 30.0 },
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FieldProfile::Attractor => "Attractor",
+            FieldProfile::Repulsor => "Repulsor",
+            FieldProfile::Vortex => "Vortex",
+            FieldProfile::Wind { .. } => "Wind",
+        }
+    }
+}
+
+/// Fraction of the vortex strength pulling bodies towards its axis, so they orbit instead of being flung out
+const VORTEX_INWARD_PULL: f32 = 0.5;
+
+/// Rays of the last blast that hit static geometry, drawn by `draw_blast_occlusion`
+#[derive(Resource, Default)]
+pub struct BlastOcclusionRays {
+    /// Blast point, point where static geometry was hit, and the body behind it
+    rays: Vec<(Vec3, Vec3, Vec3)>,
+    /// Time left before the rays are cleared
+    remaining: f32,
+}
+
+/// How long the occluded rays of a blast stay on screen, in seconds
+const OCCLUSION_RAYS_DURATION: f32 = 2.0;
+
+/// How the blast force relates to the mass of the bodies it hits
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BlastResponse {
+    /// The force is an impulse, so heavy bodies are pushed less than light ones
+    Impulse,
+    /// The force is scaled by the body mass, so every body gets the same velocity change
+    VelocityChange,
+}
+
+/// Amount of samples in a `BlastFalloff::Custom` curve
+pub const CUSTOM_FALLOFF_SAMPLES: usize = 5;
+
+/// How the blast force fades from the blast point to the edge of the blast radius
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BlastFalloff {
+    Linear,
+    Quadratic,
+    /// Falls off with the square of the distance, outside of a small core so it stays finite at the centre
+    InverseSquare,
+    Constant,
+    /// Evenly spaced samples from the centre (first) to the edge (last), linearly interpolated
+    Custom([f32; CUSTOM_FALLOFF_SAMPLES]),
+}
+
+/// Fraction of the blast radius where the inverse-square falloff starts
+const INVERSE_SQUARE_CORE: f32 = 0.1;
+
+impl BlastFalloff {
+    /// Every profile, `Custom` starts out as an ease-out curve that can then be edited
+    pub const ALL: [BlastFalloff; 5] = [
+        BlastFalloff::Linear,
+        BlastFalloff::Quadratic,
+        BlastFalloff::InverseSquare,
+        BlastFalloff::Constant,
+        BlastFalloff::Custom([1.0, 0.9, 0.7, 0.4, 0.0]),
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            BlastFalloff::Linear => "Linear",
+            BlastFalloff::Quadratic => "Quadratic",
+            BlastFalloff::InverseSquare => "Inverse Square",
+            BlastFalloff::Constant => "Constant",
+            BlastFalloff::Custom(_) => "Custom",
+        }
+    }
+
+    /// The profile after this one in `ALL`, wrapping around
+    pub fn next(&self) -> Self {
+        let index = Self::ALL.iter().position(|falloff| falloff.label() == self.label()).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Fraction of the max force applied at `t`, the distance divided by the blast radius (0 at the centre, 1 at the edge)
+    pub fn factor(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            BlastFalloff::Linear => 1.0 - t,
+            BlastFalloff::Quadratic => (1.0 - t).powi(2),
+            BlastFalloff::InverseSquare => (INVERSE_SQUARE_CORE / t.max(INVERSE_SQUARE_CORE)).powi(2),
+            BlastFalloff::Constant => 1.0,
+            BlastFalloff::Custom(samples) => {
+                let position = t * (CUSTOM_FALLOFF_SAMPLES - 1) as f32;
+                let index = (position as usize).min(CUSTOM_FALLOFF_SAMPLES - 2);
+                samples[index].lerp(samples[index + 1], position - index as f32)
+            }
+        }
+    }
+}
+
+/// Which way the blast pushes the bodies in its radius
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BlastDirection {
+    /// Away from the blast point
+    Radial,
+    /// Along the camera forward, only for bodies within `half_angle` (degrees) of it as seen from the blast point
+    Cone { half_angle: f32 },
+}
+
+impl BlastDirection {
+    pub const DEFAULT_CONE: BlastDirection = BlastDirection::Cone { half_angle: 30.0 };
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            BlastDirection::Radial => "Radial",
+            BlastDirection::Cone { .. } => "Cone",
+        }
+    }
+
+    /// Direction a body at `offset` from the blast point is pushed in, `None` when it is outside of the cone
+    pub fn push(&self, offset: Vec3, forward: Vec3) -> Option<Vec3> {
+        match *self {
+            BlastDirection::Radial => Some(offset.normalize()),
+            BlastDirection::Cone { half_angle } => (offset.angle_between(forward) <= half_angle.to_radians()).then_some(forward),
+        }
+    }
+}
+
+/// Furthest a body can be grabbed from, matches the range of `scroll_control`
+const GRAB_REACH: f32 = 50.0;
+
+/// Furthest surface the cursor snaps to, past it the cursor falls back to the scroll distance
+const SNAP_REACH: f32 = 500.0;
+
+/// Snaps the impulse and wrecker cursors to the first surface under the mouse instead of the scroll distance
+#[derive(Resource)]
+pub struct CursorSnap {
+    pub enabled: bool,
+    /// Distance kept from the surface along its normal, e.g. so the wrecker ball sits on top of it
+    pub normal_offset: f32,
+}
+impl Default for CursorSnap {
+    fn default() -> Self {
+        Self { enabled: false, normal_offset: 0.5 }
+    }
+}
+
+/// Kinematic body without a collider that follows the cursor in Grab mode, grabbed bodies are jointed to it
+#[derive(Component)]
+pub struct GrabAnchor;
+
+/// Joint holding the grabbed body, only exists while the left mouse button is held in Grab mode
+#[derive(Component)]
+pub struct GrabJoint;
+
+#[derive(Resource)]
+pub struct GrabSettings {
+    /// Softness of the joint holding the body (inverse of its stiffness), 0 holds it rigidly
+    pub compliance: f32,
+}
+impl Default for GrabSettings {
+    fn default() -> Self {
+        Self { compliance: 0.0001 }
+    }
+}
+
+/// Camera the cursor rays are cast from, either the playground or the levels fly camera
+pub type CursorCamera<'w, 's> = Single<'w, 's, (&'static Camera, &'static GlobalTransform), Or<(With<FlyCamera>, With<LevelsFlyCamera>)>>;
+
+/// Entities following the cursor, which the cursor ray should go through
+type CursorEntities = Or<(With<ImpulseCursor>, With<WreckerCursor>, With<GrabAnchor>)>;
+/// Bodies pushed by the impulse blast
+type BlastTargets = (With<RigidBody>, Without<WreckerCursor>);
+/// The wrecker ball itself, its label also has a `WreckerCursor`
+type WreckerBody = (With<WreckerCursor>, With<RigidBody>);
+type WreckingBallEntities = Or<(With<WreckingBall>, With<WreckingBallChain>)>;
+
+/// Everything needed to point at the scene with the mouse, shared by the interaction modes
+#[derive(SystemParam)]
+pub struct SceneCursor<'w, 's> {
+    window: Single<'w, 's, &'static Window>,
+    camera: CursorCamera<'w, 's>,
+    mouse_input: Res<'w, ButtonInput<MouseButton>>,
+    egui_ctx: Res<'w, EguiWantsInput>,
+    ui_interactions: Query<'w, 's, &'static Interaction>,
+    distance: ResMut<'w, CursorDistance>,
+    snap: Res<'w, CursorSnap>,
+    spatial_query: SpatialQuery<'w, 's>,
+    /// The cursors themselves, never hit by the cursor ray
+    cursor_entities: Query<'w, 's, Entity, CursorEntities>,
+}
+
+impl SceneCursor<'_, '_> {
+    /// Ray from the camera through the cursor, `None` when the cursor is outside of the window
+    pub fn ray(&self) -> Option<Ray3d> {
+        let (camera, camera_transform) = *self.camera;
+        let cursor_position = self.window.cursor_position()?;
+        camera.viewport_to_world(camera_transform, cursor_position).ok()
+    }
+
+    /// Direction the camera is looking at
+    pub fn forward(&self) -> Vec3 {
+        self.camera.1.forward().as_vec3()
+    }
+
+    /// Point on the cursor ray at the `CursorDistance` set with `scroll_control`
+    pub fn point(&self) -> Option<Vec3> {
+        self.ray().map(|ray| ray.get_point(self.distance.0))
+    }
+
+    /// First collider under the cursor within `max_distance`, ignoring the cursors themselves
+    pub fn hit(&self, max_distance: f32) -> Option<(Ray3d, RayHitData)> {
+        let ray = self.ray()?;
+        let filter = SpatialQueryFilter::from_excluded_entities(self.cursor_entities);
+        let hit = self.spatial_query.cast_ray(ray.origin, ray.direction, max_distance, true, &filter)?;
+        Some((ray, hit))
+    }
+
+    /// First point where the segment from `from` to `to` hits a collider, ignoring the cursors themselves
+    pub fn cast(&self, from: Vec3, to: Vec3) -> Option<Vec3> {
+        let direction = Dir3::new(to - from).ok()?;
+        let filter = SpatialQueryFilter::from_excluded_entities(self.cursor_entities);
+        let hit = self.spatial_query.cast_ray(from, direction, from.distance(to), true, &filter)?;
+        Some(from + direction * hit.distance)
+    }
+
+    /// First collider between `from` and `to` that `is_occluder` accepts, ignoring the cursors themselves
+    pub fn occluder(&self, from: Vec3, to: Vec3, is_occluder: &dyn Fn(Entity) -> bool) -> Option<Vec3> {
+        let direction = Dir3::new(to - from).ok()?;
+        let filter = SpatialQueryFilter::from_excluded_entities(self.cursor_entities);
+        let hit = self.spatial_query.cast_ray_predicate(from, direction, from.distance(to), true, &filter, is_occluder)?;
+        Some(from + direction * hit.distance)
+    }
+
+    /// Where the impulse and wrecker cursors go
+    /// - With `CursorSnap` enabled, on the first surface under the mouse (pushed out along its normal)
+    /// - Otherwise, or when the ray hits nothing, at the scroll distance like `point`
+    pub fn target_point(&self) -> Option<Vec3> {
+        if self.snap.enabled
+            && let Some((ray, hit)) = self.hit(SNAP_REACH)
+        {
+            return Some(ray.get_point(hit.distance) + hit.normal * self.snap.normal_offset);
+        }
+        self.point()
+    }
+
+    /// Moves the cursor point along the ray, within the same range as `scroll_control`
+    pub fn set_distance(&mut self, distance: f32) {
+        self.distance.0 = distance.clamp(1.0, 50.0);
+    }
+
+    /// True when the cursor is over an egui window or a bevy UI button, clicks there shouldn't reach the scene
+    pub fn over_ui(&self) -> bool {
+        self.egui_ctx.is_pointer_over_area()
+            || self.ui_interactions.iter().any(|interaction| *interaction != Interaction::None)
+    }
+
+    /// Left click that landed on the scene rather than on the UI
+    pub fn just_clicked(&self) -> bool {
+        self.mouse_input.just_pressed(MouseButton::Left) && !self.over_ui()
+    }
+
+    /// Left mouse button held down over the scene rather than over the UI
+    pub fn held(&self) -> bool {
+        self.mouse_input.pressed(MouseButton::Left) && !self.over_ui()
     }
 }
 
 pub fn draw_impulse_cursor(
-    distance: Res<CursorDistance>,
+    cursor: SceneCursor,
     mut labels: Query<(&mut Node, &ExampleLabel)>,
     mut text: Single<&mut Text, With<ImpulseCoords>>,
     labeled: Query<&GlobalTransform>,
-    camera_query: Single<(&Camera, &GlobalTransform), Or<(With<FlyCamera>, With<LevelsFlyCamera>)>>,
-    window: Single<&Window>,
+    camera_query: CursorCamera,
     mut cursor_entity_query: Query<&mut Transform, With<ImpulseCursor>>,
 ) {
     // If the system runs, the mode is Impulse, so we draw the cursor
-    let current_distance = distance.0;
     let (camera, camera_transform) = *camera_query;
     let Ok(mut cursor_entity_transform) = cursor_entity_query.single_mut() else {
         return;
     };
-    if let Some(cursor_position) = window.cursor_position()
-        && let Ok(ray) = camera.viewport_to_world(camera_transform, cursor_position)
-    {
-        // Calculate the point on the ray at the current stored distance
-        let point = ray.get_point(current_distance);
+    // Point on the surface under the cursor (when snapping) or on the ray at the current stored distance
+    if let Some(point) = cursor.target_point() {
 
         let position_vector = point;
 
@@ -91,38 +534,43 @@ pub fn draw_impulse_cursor(
 }
 
 pub fn draw_wrecker_cursor(
-    distance: Res<CursorDistance>,
+    cursor: SceneCursor,
     mut labels: Query<(&mut Node, &ExampleLabel)>,
     mut text: Single<&mut Text, With<WreckerCoords>>,
     labeled: Query<&GlobalTransform>,
-    camera_query: Single<(&Camera, &GlobalTransform), Or<(With<FlyCamera>, With<LevelsFlyCamera>)>>,
-    window: Single<&Window>,
+    camera_query: CursorCamera,
     time: Res<Time>,
     mut wrecker_entity_query: Query<(&mut LinearVelocity, &mut Transform), With<WreckerCursor>>,
+    wrecker_settings: Res<WreckerSettings>,
 ) {
     // If the system runs, the mode is Impulse, so we draw the cursor
-    let current_distance = distance.0;
     let (camera, camera_transform) = *camera_query;
     let Ok((mut linear_velocity, wrecker_entity_transform)) = wrecker_entity_query.single_mut() else {
         return;
     };
-    if let Some(cursor_position) = window.cursor_position()
-        && let Ok(ray) = camera.viewport_to_world(camera_transform, cursor_position)
-    {
-        // Calculate the point on the ray at the current stored distance
-        let point = ray.get_point(current_distance);
+    // Point on the surface under the cursor (when snapping) or on the ray at the current stored distance
+    if let Some(point) = cursor.target_point() {
 
         // Calculate the distance vector to the target point
         let target_position = point;
         let current_position = wrecker_entity_transform.translation;
         let delta = target_position - current_position;
 
-        // Caculate the required velocity
-        let velocity = delta / time.delta_secs();
-        // info!("Velocity: {}", velocity);
+        // Caculate the required velocity, capped so a mouse flick can't launch the wrecker
+        // - The speed also stays low enough to brake within the max acceleration, so it doesn't overshoot the target
+        let dt = time.delta_secs();
+        if dt > 0.0 {
+            let distance = delta.length();
+            let target_speed = (distance / dt)
+                .min(wrecker_settings.max_speed)
+                .min((2.0 * wrecker_settings.max_acceleration * distance).sqrt());
+            let target_velocity = delta.normalize_or_zero() * target_speed;
+            let velocity = linear_velocity.0 + (target_velocity - linear_velocity.0).clamp_length_max(wrecker_settings.max_acceleration * dt);
+            // info!("Velocity: {}", velocity);
 
-        // Set the LinearVelocity component
-        linear_velocity.0 = velocity;
+            // Set the LinearVelocity component
+            linear_velocity.0 = velocity;
+        }
 
         let position_vector = current_position;
 
@@ -137,7 +585,7 @@ pub fn draw_wrecker_cursor(
                 node.top = px(viewport_position.unwrap_or(Vec2::new(-100.0, -100.0)).y); // gracefully handle instances where viewport position in x and y are not available
                 node.left = px(viewport_position.unwrap_or(Vec2::new(-100.0, -100.0)).x);
 
-                text.0 = format!("┌─ Wrecker: {}", position_text.clone());
+                text.0 = format!("┌─ Wrecker: {} {:.1} m/s", position_text.clone(), linear_velocity.length());
             } else { // gracefully handle instances where viewport position may not be available
                 node.top = px(-100.0);
                 node.left = px(-100.0);
@@ -148,57 +596,533 @@ pub fn draw_wrecker_cursor(
     }
 }
 
+/// - The impulse is applied at the point of the body's colliders closest to the blast, so off-center hits make bodies spin
+/// - With occlusion enabled, static geometry between the blast and a body weakens or blocks its impulse
 pub fn apply_force(
-    distance: Res<CursorDistance>,
-    mut forces: Query<(&Transform, Forces), (With<RigidBody>, Without<WreckerCursor>)>,
+    cursor: SceneCursor,
+    mut forces: Query<(Entity, &Transform, &ComputedMass, Forces), BlastTargets>,
+    colliders: Query<(&ColliderOf, &Collider, &Position, &Rotation)>,
+    rigid_bodies: Query<&RigidBody>,
     impulse_settings: Res<ImpulseSettings>,
-    window: Single<&Window>,
-    camera_query: Single<(&Camera, &GlobalTransform), Or<(With<FlyCamera>, With<LevelsFlyCamera>)>>,
-    mouse_input: Res<ButtonInput<MouseButton>>,
-    egui_ctx: Res<EguiWantsInput>,
-    ui_interactions: Query<&Interaction>,
+    mut occlusion_rays: ResMut<BlastOcclusionRays>,
 ) {
-    if egui_ctx.is_pointer_over_area() {
+    if !cursor.just_clicked() {
         return;
     }
-    for interaction in &ui_interactions {
-        if *interaction != Interaction::None {
-            return;
+    occlusion_rays.rays.clear();
+    occlusion_rays.remaining = OCCLUSION_RAYS_DURATION;
+    // Colliders without a body are static too
+    let is_static = |entity: Entity| {
+        colliders
+            .get(entity)
+            .map_or(true, |(collider_of, ..)| rigid_bodies.get(collider_of.body).is_ok_and(RigidBody::is_static))
+    };
+    let forward = cursor.forward();
+    if let Some(point) = cursor.target_point() {
+        // Closest point to the blast on each body, a body can have several colliders
+        let mut closest_points: HashMap<Entity, Vec3> = HashMap::new();
+        for (collider_of, collider, position, rotation) in &colliders {
+            let (projected, _) = collider.project_point(*position, *rotation, point, true);
+            closest_points
+                .entry(collider_of.body)
+                .and_modify(|closest| {
+                    if projected.distance_squared(point) < closest.distance_squared(point) {
+                        *closest = projected;
+                    }
+                })
+                .or_insert(projected);
         }
-    }
-    
-    // let current_distance = distance.0;
-    let (camera, camera_transform) = *camera_query;
-    if let Some(cursor_position) = window.cursor_position()
-        && let Ok(ray) = camera.viewport_to_world(camera_transform, cursor_position)
-        && mouse_input.just_pressed(MouseButton::Left)
-    {
-        let point = ray.get_point(distance.0);
-        for (body_transform , mut impulse_comp) in &mut forces {
-            // Vector pointing from point to rigid_body
-            let direction_vec = body_transform.translation - point;
+
+        for (entity, body_transform, mass, mut impulse_comp) in &mut forces {
+            let hit_point = closest_points.get(&entity).copied().unwrap_or(body_transform.translation);
+
+            // Vector pointing from point to the hit point, or to the body origin when the blast is inside the body
+            let mut direction_vec = hit_point - point;
+            if direction_vec.length_squared() < f32::EPSILON {
+                direction_vec = body_transform.translation - point;
+            }
 
             // Set distance
-            let distance = direction_vec.length();
+            let distance = hit_point.distance(point);
             // info!("Distance: {}", distance);
 
-            // Check radius and avoid division by zero if distance is 0
-            if distance > 0.0 && distance < impulse_settings.blast_radius {
-                // Linear falloff factor: 1.0 at center, 0.0 at edge
-                let falloff = 1.0 - (distance / impulse_settings.blast_radius);
+            // Check radius and skip bodies without a direction to push them in
+            if distance < impulse_settings.blast_radius && direction_vec != Vec3::ZERO {
+                // Bodies outside of the cone aren't affected
+                let Some(direction) = impulse_settings.direction.push(direction_vec, forward) else {
+                    continue;
+                };
+
+                // Falloff factor: 1.0 at center, fading towards the edge depending on the profile
+                let falloff = impulse_settings.falloff.factor(distance / impulse_settings.blast_radius);
 
                 // Calculate the impulse vector: Direction * Max Force * Falloff
-                // info!("Normalized direction vector: {}", direction_vec.normalize());
-                let impulse = direction_vec.normalize() * impulse_settings.max_force * falloff;
+                let mut impulse = direction * impulse_settings.max_force * falloff;
+                if impulse_settings.occlusion
+                    && let Some(occluded_at) = cursor.occluder(point, hit_point, &is_static)
+                {
+                    occlusion_rays.rays.push((point, occluded_at, hit_point));
+                    impulse *= impulse_settings.occluded_force;
+                }
+                if impulse_settings.response == BlastResponse::VelocityChange && mass.value().is_finite() {
+                    impulse *= mass.value();
+                }
 
-                // Apply the impulse
-                impulse_comp.apply_linear_impulse(impulse);
+                // Apply the impulse at the hit point, inducing torque when it is off-center
+                impulse_comp.apply_linear_impulse_at_point(impulse, hit_point);
             }
         }
     }
 
 }
 
+/// Applies the `FieldSettings` force to the bodies around the cursor while the left mouse button is held
+/// - The force is an acceleration (applied as a per-frame impulse scaled by mass), so heavy and light bodies move alike
+/// - Bodies outside of the radius are skipped on a squared distance check before their forces are touched
+/// - Sleeping bodies are only woken when the field changes their velocity by more than their `SleepThreshold` this frame,
+///   otherwise the weak edge of the field would keep every body it covers awake
+pub fn apply_field(
+    cursor: SceneCursor,
+    mut bodies: Query<(&Position, &ComputedMass, &SleepThreshold, Has<Sleeping>, Forces), BlastTargets>,
+    field_settings: Res<FieldSettings>,
+    time: Res<Time>,
+    mut gizmos: Gizmos,
+) {
+    if !cursor.held() {
+        return;
+    }
+    let Some(point) = cursor.target_point() else {
+        return;
+    };
+    let forward = cursor.forward();
+    let radius = field_settings.radius;
+    gizmos.sphere(Isometry3d::from_translation(point), radius, color::palettes::css::DEEP_SKY_BLUE);
+
+    for (position, mass, sleep_threshold, sleeping, mut forces) in &mut bodies {
+        let offset = position.0 - point;
+        let distance_squared = offset.length_squared();
+        if distance_squared >= radius * radius {
+            continue;
+        }
+        let distance = distance_squared.sqrt();
+        let direction = offset.normalize_or_zero();
+        let acceleration = match field_settings.profile {
+            FieldProfile::Attractor => -direction,
+            FieldProfile::Repulsor => direction,
+            FieldProfile::Vortex => {
+                // Only the part of the offset perpendicular to the camera axis matters
+                let radial = offset.reject_from_normalized(forward).normalize_or_zero();
+                forward.cross(radial) - radial * VORTEX_INWARD_PULL
+            }
+            FieldProfile::Wind { half_angle } => {
+                if offset.angle_between(forward) > half_angle.to_radians() {
+                    continue;
+                }
+                forward
+            }
+        } * field_settings.strength * (1.0 - distance / radius);
+        if !mass.value().is_finite() || acceleration == Vec3::ZERO {
+            continue;
+        }
+        let velocity_change = acceleration * time.delta_secs();
+        if sleeping && velocity_change.length() < sleep_threshold.linear {
+            continue;
+        }
+        forces.apply_linear_impulse(velocity_change * mass.value());
+    }
+}
+
+/// Where a shot fired now starts and how fast it goes, along the cursor ray
+fn launch(cursor: &SceneCursor, settings: &LauncherSettings) -> Option<(Vec3, Vec3)> {
+    let ray = cursor.ray()?;
+    Some((ray.get_point(LAUNCH_OFFSET), ray.direction * settings.speed))
+}
+
+/// Draws the path the next shot would follow under gravity, up to the first collider on the way
+pub fn draw_launch_trajectory(
+    cursor: SceneCursor,
+    settings: Res<LauncherSettings>,
+    gravity: Res<Gravity>,
+    mut gizmos: Gizmos,
+) {
+    let Some((origin, velocity)) = launch(&cursor, &settings) else {
+        return;
+    };
+    let steps = (TRAJECTORY_DURATION / TRAJECTORY_STEP) as usize;
+    let mut previous = origin;
+    for step in 1..=steps {
+        let t = step as f32 * TRAJECTORY_STEP;
+        let point = origin + velocity * t + 0.5 * gravity.0 * t * t;
+        if let Some(hit) = cursor.cast(previous, point) {
+            gizmos.line(previous, hit, color::palettes::css::ORANGE);
+            gizmos.sphere(Isometry3d::from_translation(hit), 0.15, color::palettes::css::ORANGE);
+            return;
+        }
+        gizmos.line(previous, point, color::palettes::css::ORANGE);
+        previous = point;
+    }
+}
+
+/// Fires projectiles along the cursor ray while the left mouse button is held, at the `LauncherSettings` fire rate
+pub fn fire_projectile(
+    mut commands: Commands,
+    cursor: SceneCursor,
+    settings: Res<LauncherSettings>,
+    catalog: Res<SceneCatalog>,
+    time: Res<Time>,
+    assets: Res<ProjectileAssets>,
+    mut cooldown: Local<f32>,
+) {
+    *cooldown -= time.delta_secs();
+    if !cursor.held() || *cooldown > 0.0 {
+        return;
+    }
+    let Some((origin, velocity)) = launch(&cursor, &settings) else {
+        return;
+    };
+    *cooldown = 1.0 / settings.fire_rate;
+
+    let transform = Transform::from_translation(origin).with_scale(Vec3::splat(settings.size));
+    let (mesh, collider) = match &settings.projectile {
+        LauncherProjectile::Sphere => (assets.sphere.clone(), Collider::sphere(0.5)),
+        LauncherProjectile::Cube => (assets.cube.clone(), Collider::cuboid(1.0, 1.0, 1.0)),
+        LauncherProjectile::Shape(name) => {
+            if let Some(mut entity) = catalog.spawn(&mut commands, name, (transform, Projectile, SceneProjectileLaunch { velocity, mass: settings.mass })) {
+                entity.observe(launch_scene_projectile);
+            }
+            return;
+        }
+    };
+    commands.spawn((
+        Mesh3d(mesh),
+        MeshMaterial3d(assets.material.clone()),
+        transform,
+        collider,
+        RigidBody::Dynamic,
+        Mass(settings.mass),
+        NoAutoMass,
+        LinearVelocity(velocity),
+        SweptCcd::default(),
+        Projectile,
+    ));
+}
+
+/// Hands the launch velocity and mass of a scene projectile to its bodies
+fn launch_scene_projectile(
+    trigger: On<SceneInstanceReady>,
+    mut commands: Commands,
+) {
+    // Run as a command so it comes after the pipeline observer's commands that create the bodies
+    commands.run_system_cached_with(apply_scene_projectile, trigger.entity);
+}
+
+fn apply_scene_projectile(
+    In(root): In<Entity>,
+    mut commands: Commands,
+    launches: Query<&SceneProjectileLaunch>,
+    children: Query<&Children>,
+    bodies: Query<(), With<RigidBody>>,
+) {
+    let Ok(scene_launch) = launches.get(root) else {
+        return;
+    };
+    for body in children.iter_descendants(root).filter(|entity| bodies.contains(*entity)) {
+        commands.entity(body).insert((
+            LinearVelocity(scene_launch.velocity),
+            Mass(scene_launch.mass),
+            NoAutoMass,
+            SweptCcd::default(),
+        ));
+    }
+}
+
+/// Removes every projectile, from the menu or when leaving the playground
+pub fn despawn_projectiles(
+    mut commands: Commands,
+    projectiles: Query<Entity, With<Projectile>>,
+) {
+    for entity in &projectiles {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Hangs a `WreckingBall` below the `WreckerCursor` if there isn't one already
+/// - The chain is a distance joint that only limits the max length, so the ball swings freely and hits with its own momentum
+pub fn spawn_wrecking_ball(
+    mut commands: Commands,
+    wrecker: Single<(Entity, &Transform), WreckerBody>,
+    balls: Query<(), With<WreckingBall>>,
+    settings: Res<WreckingBallSettings>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    if !balls.is_empty() {
+        return;
+    }
+    let (wrecker, wrecker_transform) = *wrecker;
+    let ball = commands.spawn((
+        Mesh3d(meshes.add(Sphere::new(WRECKING_BALL_RADIUS))),
+        MeshMaterial3d(materials.add(Color::srgb(0.2, 0.2, 0.25))),
+        Transform::from_translation(wrecker_transform.translation - Vec3::Y * settings.chain_length),
+        Collider::sphere(WRECKING_BALL_RADIUS),
+        RigidBody::Dynamic,
+        Mass(settings.ball_mass),
+        NoAutoMass,
+        SweptCcd::default(),
+        WreckingBall,
+    )).id();
+    commands.spawn((
+        DistanceJoint::new(wrecker, ball)
+            .with_local_anchor2(Vec3::Y * WRECKING_BALL_RADIUS)
+            .with_limits(0.0, settings.chain_length),
+        JointCollisionDisabled,
+        WreckingBallChain,
+        // Despawned along with the wrecker when leaving the playground
+        ChildOf(wrecker),
+    ));
+}
+
+/// Removes the `WreckingBall` and its chain, when leaving Wrecking Ball mode or the scene
+pub fn despawn_wrecking_ball(
+    mut commands: Commands,
+    wrecking_ball: Query<Entity, WreckingBallEntities>,
+) {
+    // The chain may already be gone along with the wrecker when leaving the scene
+    for entity in &wrecking_ball {
+        commands.entity(entity).try_despawn();
+    }
+}
+
+/// Applies changes of the `WreckingBallSettings` to the hanging ball
+pub fn update_wrecking_ball(
+    settings: Res<WreckingBallSettings>,
+    mut chains: Query<&mut DistanceJoint, With<WreckingBallChain>>,
+    mut balls: Query<&mut Mass, With<WreckingBall>>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    for mut chain in &mut chains {
+        chain.limits.max = settings.chain_length;
+    }
+    for mut mass in &mut balls {
+        mass.0 = settings.ball_mass;
+    }
+}
+
+/// Draws the chain of the wrecking ball as a line of links from the wrecker to the top of the ball
+pub fn draw_wrecking_ball_chain(
+    chains: Query<&DistanceJoint, With<WreckingBallChain>>,
+    bodies: Query<(&Position, &Rotation)>,
+    mut gizmos: Gizmos,
+) {
+    for chain in &chains {
+        let (Ok((start, _)), Ok((ball_position, ball_rotation))) = (bodies.get(chain.body1), bodies.get(chain.body2)) else {
+            continue;
+        };
+        let end = ball_position.0 + ball_rotation.0 * chain.local_anchor2().unwrap_or_default();
+        let links = (start.0.distance(end) / CHAIN_LINK_SPACING).ceil().max(1.0) as usize;
+        gizmos.line(start.0, end, color::palettes::css::DIM_GRAY);
+        for link in 0..=links {
+            let point = start.0.lerp(end, link as f32 / links as f32);
+            gizmos.sphere(Isometry3d::from_translation(point), 0.08, color::palettes::css::GRAY);
+        }
+    }
+}
+
+/// Draws the rays of the last blast that were occluded, red up to the static geometry and grey behind it
+pub fn draw_blast_occlusion(
+    mut gizmos: Gizmos,
+    time: Res<Time>,
+    mut occlusion_rays: ResMut<BlastOcclusionRays>,
+    impulse_settings: Res<ImpulseSettings>,
+) {
+    if occlusion_rays.remaining <= 0.0 {
+        return;
+    }
+    occlusion_rays.remaining -= time.delta_secs();
+    if !impulse_settings.show_occlusion {
+        return;
+    }
+    for &(point, occluded_at, body_point) in &occlusion_rays.rays {
+        gizmos.line(point, occluded_at, color::palettes::css::RED);
+        gizmos.sphere(Isometry3d::from_translation(occluded_at), 0.1, color::palettes::css::RED);
+        gizmos.line(occluded_at, body_point, color::palettes::css::GRAY);
+    }
+}
+
+/// Grabs the dynamic body under the cursor by jointing it to the `GrabAnchor` at the hit point
+/// - The hold distance starts at the hit distance and can then be changed with `scroll_control`
+pub fn grab_body(
+    mut commands: Commands,
+    mut cursor: SceneCursor,
+    colliders: Query<&ColliderOf>,
+    bodies: Query<(&RigidBody, &Position, &Rotation), Without<GrabAnchor>>,
+    mut anchor: Single<(Entity, &mut Transform), With<GrabAnchor>>,
+    grab_settings: Res<GrabSettings>,
+) {
+    if !cursor.just_clicked() {
+        return;
+    }
+    let Some((ray, hit)) = cursor.hit(GRAB_REACH) else {
+        return;
+    };
+    // The hit collider can be a child of the body it belongs to
+    let body = colliders.get(hit.entity).map_or(hit.entity, |collider_of| collider_of.body);
+    let Ok((rigid_body, position, rotation)) = bodies.get(body) else {
+        return;
+    };
+    if !rigid_body.is_dynamic() {
+        return;
+    }
+
+    let point = ray.get_point(hit.distance);
+    cursor.set_distance(hit.distance);
+    // Start the anchor on the hit point so the body isn't yanked towards the previous cursor point
+    anchor.1.translation = point;
+    commands.entity(body).remove::<Sleeping>();
+    commands.spawn((
+        DistanceJoint::new(anchor.0, body)
+            .with_local_anchor2(rotation.inverse() * (point - position.0))
+            .with_limits(0.0, 0.0)
+            .with_compliance(grab_settings.compliance),
+        JointCollisionDisabled,
+        GrabJoint,
+        // Despawned along with the anchor when leaving the playground
+        ChildOf(anchor.0),
+    ));
+}
+
+/// Moves the `GrabAnchor` to the cursor point through its velocity (like the wrecker) so the held body follows smoothly
+pub fn drag_grab_anchor(
+    cursor: SceneCursor,
+    time: Res<Time>,
+    mut anchor: Single<(&Transform, &mut LinearVelocity), With<GrabAnchor>>,
+    grabs: Query<&DistanceJoint, With<GrabJoint>>,
+    bodies: Query<(&Position, &Rotation)>,
+    mut gizmos: Gizmos,
+) {
+    let (anchor_transform, ref mut linear_velocity) = *anchor;
+    let anchor_position = anchor_transform.translation;
+    linear_velocity.0 = match cursor.point() {
+        Some(point) if time.delta_secs() > 0.0 => (point - anchor_position) / time.delta_secs(),
+        _ => Vec3::ZERO,
+    };
+
+    // Line from the anchor to the point the body is held by
+    for joint in &grabs {
+        let Ok((position, rotation)) = bodies.get(joint.body2) else {
+            continue;
+        };
+        let held_point = position.0 + rotation.0 * joint.local_anchor2().unwrap_or_default();
+        gizmos.line(anchor_position, held_point, Color::WHITE);
+        gizmos.sphere(Isometry3d::from_translation(anchor_position), 0.1, Color::WHITE);
+    }
+}
+
+/// Lets go of the grabbed body once the button is released or another mode is picked, the body keeps its velocity
+pub fn release_grab(
+    mut commands: Commands,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    interaction_mode: Res<InteractionMode>,
+    grabs: Query<Entity, With<GrabJoint>>,
+    mut anchor: Single<&mut LinearVelocity, With<GrabAnchor>>,
+) {
+    let grabbing = interaction_mode.0 == InteractionModeType::Grab;
+    if !grabbing {
+        anchor.0 = Vec3::ZERO;
+    }
+    if grabbing && mouse_input.pressed(MouseButton::Left) {
+        return;
+    }
+    for entity in &grabs {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Pins the clicked body according to the `PinSettings`, or unpins it when it is already pinned
+pub fn pin_body(
+    mut commands: Commands,
+    cursor: SceneCursor,
+    settings: Res<PinSettings>,
+    colliders: Query<&ColliderOf>,
+    bodies: Query<(&RigidBody, Option<&LockedAxes>, Option<&Pinned>)>,
+) {
+    if !cursor.just_clicked() {
+        return;
+    }
+    let Some((_, hit)) = cursor.hit(PIN_REACH) else {
+        return;
+    };
+    // The hit collider can be a child of the body it belongs to
+    let body = colliders.get(hit.entity).map_or(hit.entity, |collider_of| collider_of.body);
+    let Ok((rigid_body, locked_axes, pinned)) = bodies.get(body) else {
+        return;
+    };
+    if let Some(pinned) = pinned {
+        unpin(&mut commands, body, pinned);
+        return;
+    }
+    // Static and kinematic bodies already stay in place (or are moved by something else, like the wrecker)
+    if !rigid_body.is_dynamic() {
+        return;
+    }
+
+    match settings.mode {
+        PinMode::Freeze => freeze(&mut commands, body, *rigid_body, locked_axes.copied()),
+        PinMode::LockAxes => lock_axes(&mut commands, body, *rigid_body, locked_axes.copied(), settings.locked_axes()),
+    }
+}
+
+/// Turns a body static, keeping its type and locked axes in `Pinned` so `unpin` can give them back
+pub fn freeze(commands: &mut Commands, body: Entity, rigid_body: RigidBody, locked_axes: Option<LockedAxes>) {
+    // `RigidBody` is immutable, changing it means inserting it again
+    commands.entity(body).insert((Pinned { rigid_body, locked_axes }, RigidBody::Static));
+}
+
+/// Locks the `axes` of a body and stops it, keeping its type and locked axes in `Pinned` so `unpin` can give them back
+fn lock_axes(commands: &mut Commands, body: Entity, rigid_body: RigidBody, locked_axes: Option<LockedAxes>, axes: LockedAxes) {
+    commands.entity(body).insert((Pinned { rigid_body, locked_axes }, axes, LinearVelocity::ZERO, AngularVelocity::ZERO));
+}
+
+/// Gives a pinned or frozen body back the type and locked axes it had before
+pub fn unpin(commands: &mut Commands, body: Entity, pinned: &Pinned) {
+    let mut entity = commands.entity(body);
+    entity.remove::<Pinned>().insert(pinned.rigid_body);
+    match pinned.locked_axes {
+        Some(locked_axes) => entity.insert(locked_axes),
+        None => entity.remove::<LockedAxes>(),
+    };
+}
+
+/// Gives every pinned body back the type and locked axes it had before being pinned
+pub fn unpin_all(
+    mut commands: Commands,
+    pinned: Query<(Entity, &Pinned)>,
+) {
+    for (body, pinned) in &pinned {
+        unpin(&mut commands, body, pinned);
+    }
+}
+
+/// Outlines the colliders of pinned bodies, in every mode so it is clear which bodies won't move
+pub fn draw_pinned_bodies(
+    colliders: Query<(&ColliderOf, &ColliderAabb)>,
+    pinned: Query<(), With<Pinned>>,
+    mut gizmos: Gizmos,
+) {
+    for (collider_of, aabb) in &colliders {
+        if !pinned.contains(collider_of.body) {
+            continue;
+        }
+        let top = aabb.center() + Vec3::Y * aabb.size().y / 2.0;
+        gizmos.cuboid(Transform::from_translation(aabb.center()).with_scale(aabb.size()), color::palettes::css::MAGENTA);
+        // Pin head sticking out of the top of the body
+        gizmos.line(top, top + Vec3::Y * 0.5, color::palettes::css::MAGENTA);
+        gizmos.sphere(Isometry3d::from_translation(top + Vec3::Y * 0.6), 0.1, color::palettes::css::MAGENTA);
+    }
+}
+
 pub fn set_impulse_cursor_visibility<const VISIBLE: bool>(
     mut query: Query<&mut Visibility, With<ImpulseCursor>>,
 ) {
@@ -228,11 +1152,11 @@ pub fn center_cursor(
 }
 
 pub mod interactive_menu {
-    use bevy::prelude::* ;
-    use bevy_asset::{AssetServer};
+    use bevy::{ecs::system::SystemParam, prelude::*};
     use bevy_egui::{EguiContexts, egui};
-    use crate::entity_pipeline::*;
     use crate::interactions::*;
+    use crate::history::{EditRequest, PlaygroundHistory};
+    use crate::scene_catalog::{SceneCatalog, SceneKind};
 
     #[derive(Component)]
     pub struct Ground;
@@ -240,26 +1164,17 @@ pub mod interactive_menu {
     #[derive(Component)]
     pub struct EntityTag;
 
-    #[derive(Component, Clone, Copy, PartialEq, Eq, Hash, Debug)]
-    pub enum MapTag {
-        Flat,
-        Ramp,
-        RectTank,
-    }
-
-    #[derive(Component, Clone, Copy, PartialEq, Eq, Hash, Debug)]
-    pub enum ShapeTag {
-        Cube,
-        Sphere,
-        Cone,
-        Torus,
-        Cylinder,
-        SMCUBE,
-    }
-
-    #[derive(Component, Clone, Copy, PartialEq, Eq, Hash, Debug)]
-    pub enum StructureTag {
-        CubeTower
+    /// Settings of every interaction mode edited in the menu
+    #[derive(SystemParam)]
+    pub struct ModeSettings<'w> {
+        impulse: ResMut<'w, ImpulseSettings>,
+        grab: ResMut<'w, GrabSettings>,
+        field: ResMut<'w, FieldSettings>,
+        wrecking_ball: ResMut<'w, WreckingBallSettings>,
+        wrecker: ResMut<'w, WreckerSettings>,
+        launcher: ResMut<'w, LauncherSettings>,
+        pin: ResMut<'w, PinSettings>,
+        snap: ResMut<'w, CursorSnap>,
     }
 
     /// Cleans up all entities that are spawned from the interactive menu
@@ -278,14 +1193,13 @@ pub mod interactive_menu {
     pub fn interactive_menu(
         mut contexts: EguiContexts,
         mut commands: Commands,
-        asset_server: Res<AssetServer>,
+        catalog: Res<SceneCatalog>,
         keyboard_input: Res<ButtonInput<KeyCode>>,
-        maps: Query<(Entity, &MapTag)>,
-        shapes: Query<(Entity, &ShapeTag)>,
-        structures: Query<(Entity, &StructureTag)>,
         mut interaction_mode: ResMut<InteractionMode>,
-        mut impulse_settings: ResMut<ImpulseSettings>,
-        mut wrecker_query: Query<&mut Transform, With<WreckerCursor>>,
+        mut settings: ModeSettings,
+        wrecker_query: Query<&Transform, With<WreckerCursor>>,
+        projectiles: Query<Entity, With<Projectile>>,
+        mut history: PlaygroundHistory,
     ) -> Result {
         egui::Window::new("Rusty Physics Interactive Menu")
             .resizable(true)
@@ -297,7 +1211,13 @@ pub mod interactive_menu {
                 ui.label("Enable Click Mode: C");
                 ui.label("Enable Impulse Mode: I");
                 ui.label("Enable Wrecker Mode: B");
+                ui.label("Enable Grab Mode: G");
+                ui.label("Enable Field Mode: F");
+                ui.label("Enable Wrecking Ball Mode: H");
+                ui.label("Enable Launcher Mode: L");
+                ui.label("Enable Pin Mode: P");
                 ui.label("(+) and (-): Up and Down Arrow (respectively)");
+                ui.label("Undo/Redo Playground Edits: Ctrl+Z/Ctrl+Y");
 
                 ui.separator();
                 ui.label("Interactive Mode");
@@ -315,206 +1235,337 @@ pub mod interactive_menu {
                     if ui.selectable_label(is_wrecker_mode, "Wrecker Mode").clicked() || keyboard_input.just_pressed(KeyCode::KeyB) {
                         interaction_mode.0 = InteractionModeType::Wrecker;
                     }
+                    let is_grab_mode = interaction_mode.0 == InteractionModeType::Grab;
+                    if ui.selectable_label(is_grab_mode, "Grab Mode").clicked() || keyboard_input.just_pressed(KeyCode::KeyG) {
+                        interaction_mode.0 = InteractionModeType::Grab;
+                    }
+                    let is_field_mode = interaction_mode.0 == InteractionModeType::Field;
+                    if ui.selectable_label(is_field_mode, "Field Mode").clicked() || keyboard_input.just_pressed(KeyCode::KeyF) {
+                        interaction_mode.0 = InteractionModeType::Field;
+                    }
+                    let is_wrecking_ball_mode = interaction_mode.0 == InteractionModeType::WreckingBall;
+                    if ui.selectable_label(is_wrecking_ball_mode, "Wrecking Ball Mode").clicked() || keyboard_input.just_pressed(KeyCode::KeyH) {
+                        interaction_mode.0 = InteractionModeType::WreckingBall;
+                    }
+                    let is_launcher_mode = interaction_mode.0 == InteractionModeType::Launcher;
+                    if ui.selectable_label(is_launcher_mode, "Launcher Mode").clicked() || keyboard_input.just_pressed(KeyCode::KeyL) {
+                        interaction_mode.0 = InteractionModeType::Launcher;
+                    }
+                    let is_pin_mode = interaction_mode.0 == InteractionModeType::Pin;
+                    if ui.selectable_label(is_pin_mode, "Pin Mode").clicked() || keyboard_input.just_pressed(KeyCode::KeyP) {
+                        interaction_mode.0 = InteractionModeType::Pin;
+                    }
                 });
                 if interaction_mode.0 == InteractionModeType::Impulse {
                     ui.label("Impulse Settings");
                     ui.horizontal(|ui| {
-                        ui.label(format!("Blast Radius: {}", &impulse_settings.blast_radius));
-                        ui.add(egui::DragValue::new(&mut impulse_settings.blast_radius).speed(0.1));
+                        ui.label(format!("Blast Radius: {}", &settings.impulse.blast_radius));
+                        ui.add(egui::DragValue::new(&mut settings.impulse.blast_radius).speed(0.1));
                         if ui.button("-").clicked() || keyboard_input.just_pressed(KeyCode::ArrowDown) {
-                            impulse_settings.blast_radius -= 1.0;
+                            settings.impulse.blast_radius -= 1.0;
                         }
                         if ui.button("+").clicked() || keyboard_input.just_pressed(KeyCode::ArrowUp) {
-                            impulse_settings.blast_radius += 1.0;
+                            settings.impulse.blast_radius += 1.0;
                         }
                     });
                     ui.horizontal(|ui| {
-                        ui.label(format!("Max Force: {}", &impulse_settings.max_force));
-                        ui.add(egui::DragValue::new(&mut impulse_settings.max_force).speed(0.1));
+                        ui.label(format!("Max Force: {}", &settings.impulse.max_force));
+                        ui.add(egui::DragValue::new(&mut settings.impulse.max_force).speed(0.1));
                         if ui.button("-").clicked() || keyboard_input.just_pressed(KeyCode::ArrowDown) {
-                            impulse_settings.max_force -= 1.0;
+                            settings.impulse.max_force -= 1.0;
                         }
                         if ui.button("+").clicked() || keyboard_input.just_pressed(KeyCode::ArrowUp) {
-                            impulse_settings.max_force += 1.0;
+                            settings.impulse.max_force += 1.0;
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        egui::ComboBox::from_label("Falloff")
+                            .selected_text(settings.impulse.falloff.label())
+                            .show_ui(ui, |ui| {
+                                for falloff in BlastFalloff::ALL {
+                                    let selected = settings.impulse.falloff.label() == falloff.label();
+                                    if ui.selectable_label(selected, falloff.label()).clicked() && !selected {
+                                        settings.impulse.falloff = falloff;
+                                    }
+                                }
+                            });
+                    });
+                    if let BlastFalloff::Custom(samples) = &mut settings.impulse.falloff {
+                        ui.horizontal(|ui| {
+                            ui.label("Curve (center to edge):");
+                            for sample in samples.iter_mut() {
+                                ui.add(egui::DragValue::new(sample).speed(0.01).range(0.0..=1.0));
+                            }
+                        });
+                    }
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut settings.impulse.occlusion, "Occlusion")
+                            .on_hover_text("Static geometry between the blast and a body weakens its impulse");
+                        if settings.impulse.occlusion {
+                            ui.add(egui::Slider::new(&mut settings.impulse.occluded_force, 0.0..=1.0).text("Occluded Force"));
+                            ui.checkbox(&mut settings.impulse.show_occlusion, "Show Rays");
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Response:");
+                        ui.selectable_value(&mut settings.impulse.response, BlastResponse::Impulse, "Impulse")
+                            .on_hover_text("Heavy bodies are pushed less than light ones");
+                        ui.selectable_value(&mut settings.impulse.response, BlastResponse::VelocityChange, "Velocity Change")
+                            .on_hover_text("Every body gets the same velocity change regardless of its mass");
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Direction:");
+                        if ui.selectable_label(settings.impulse.direction == BlastDirection::Radial, "Radial").clicked() {
+                            settings.impulse.direction = BlastDirection::Radial;
+                        }
+                        let is_cone = matches!(settings.impulse.direction, BlastDirection::Cone { .. });
+                        if ui.selectable_label(is_cone, "Cone").clicked() && !is_cone {
+                            settings.impulse.direction = BlastDirection::DEFAULT_CONE;
+                        }
+                        if let BlastDirection::Cone { half_angle } = &mut settings.impulse.direction {
+                            ui.label("Half Angle:");
+                            ui.add(egui::DragValue::new(half_angle).speed(0.5).range(1.0..=90.0).suffix("°"));
                         }
                     });
                 }
                 if interaction_mode.0 == InteractionModeType::Wrecker {
                     ui.label("Wrecker Settings");
                     ui.horizontal(|ui| {
-                        for mut transform in &mut wrecker_query {
+                        for transform in &wrecker_query {
                             ui.label(format!("Wrecker Scale: {}", &transform.scale));
                             if ui.button("-").clicked() || keyboard_input.just_pressed(KeyCode::ArrowDown) {
-                                transform.scale -= 1.0;
+                                history.apply(EditRequest::ScaleWrecker(transform.scale - 1.0));
                             }
                             if ui.button("+").clicked() || keyboard_input.just_pressed(KeyCode::ArrowUp) {
-                                transform.scale += 1.0;
+                                history.apply(EditRequest::ScaleWrecker(transform.scale + 1.0));
+                            }
+                        }
+                    });
+                }
+                if matches!(interaction_mode.0, InteractionModeType::Wrecker | InteractionModeType::WreckingBall) {
+                    ui.horizontal(|ui| {
+                        ui.label("Max Speed:");
+                        ui.add(egui::DragValue::new(&mut settings.wrecker.max_speed).speed(0.5).range(1.0..=500.0).suffix(" m/s"));
+                        ui.label("Max Acceleration:");
+                        ui.add(egui::DragValue::new(&mut settings.wrecker.max_acceleration).speed(5.0).range(1.0..=10000.0).suffix(" m/s²"));
+                    });
+                }
+                if interaction_mode.0 == InteractionModeType::WreckingBall {
+                    ui.label("Wrecking Ball Settings");
+                    ui.horizontal(|ui| {
+                        ui.label("Chain Length:");
+                        ui.add(egui::DragValue::new(&mut settings.wrecking_ball.chain_length).speed(0.1).range(1.0..=30.0));
+                        ui.label("Ball Mass:");
+                        ui.add(egui::DragValue::new(&mut settings.wrecking_ball.ball_mass).speed(1.0).range(1.0..=1000.0));
+                    });
+                }
+                if interaction_mode.0 == InteractionModeType::Field {
+                    ui.label("Field Settings (hold left click)");
+                    ui.horizontal(|ui| {
+                        for profile in FieldProfile::ALL {
+                            let selected = settings.field.profile.label() == profile.label();
+                            if ui.selectable_label(selected, profile.label()).clicked() && !selected {
+                                settings.field.profile = profile;
                             }
                         }
                     });
+                    ui.horizontal(|ui| {
+                        ui.label("Strength:");
+                        ui.add(egui::DragValue::new(&mut settings.field.strength).speed(0.1).range(0.0..=500.0));
+                        ui.label("Radius:");
+                        ui.add(egui::DragValue::new(&mut settings.field.radius).speed(0.1).range(0.5..=100.0));
+                        if let FieldProfile::Wind { half_angle } = &mut settings.field.profile {
+                            ui.label("Half Angle:");
+                            ui.add(egui::DragValue::new(half_angle).speed(0.5).range(1.0..=90.0).suffix("°"));
+                        }
+                    });
+                }
+                if matches!(interaction_mode.0, InteractionModeType::Impulse | InteractionModeType::Wrecker | InteractionModeType::Field | InteractionModeType::WreckingBall) {
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut settings.snap.enabled, "Snap Cursor to Surfaces");
+                        ui.label("Offset:");
+                        ui.add(egui::DragValue::new(&mut settings.snap.normal_offset).speed(0.05).range(0.0..=10.0));
+                    });
+                }
+                if interaction_mode.0 == InteractionModeType::Launcher {
+                    ui.label("Launcher Settings (hold left click)");
+                    ui.horizontal_wrapped(|ui| {
+                        ui.label("Projectile:");
+                        ui.selectable_value(&mut settings.launcher.projectile, LauncherProjectile::Sphere, "Sphere");
+                        ui.selectable_value(&mut settings.launcher.projectile, LauncherProjectile::Cube, "Cube");
+                        for name in catalog.names(SceneKind::Shape) {
+                            ui.selectable_value(&mut settings.launcher.projectile, LauncherProjectile::Shape(name.to_string()), name);
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Speed:");
+                        ui.add(egui::DragValue::new(&mut settings.launcher.speed).speed(0.5).range(1.0..=500.0).suffix(" m/s"));
+                        ui.label("Mass:");
+                        ui.add(egui::DragValue::new(&mut settings.launcher.mass).speed(0.1).range(0.1..=1000.0));
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Size:");
+                        ui.add(egui::DragValue::new(&mut settings.launcher.size).speed(0.05).range(0.1..=10.0));
+                        ui.label("Fire Rate:");
+                        ui.add(egui::DragValue::new(&mut settings.launcher.fire_rate).speed(0.1).range(0.1..=30.0).suffix(" /s"));
+                    });
+                    if ui.button(format!("Clear Projectiles ({})", projectiles.iter().len())).clicked() {
+                        for entity in &projectiles {
+                            commands.entity(entity).despawn();
+                        }
+                    }
+                }
+                if interaction_mode.0 == InteractionModeType::Pin {
+                    ui.label("Pin Settings (click a body to pin or unpin it)");
+                    ui.horizontal(|ui| {
+                        ui.selectable_value(&mut settings.pin.mode, PinMode::Freeze, "Freeze")
+                            .on_hover_text("Pinned bodies become static");
+                        ui.selectable_value(&mut settings.pin.mode, PinMode::LockAxes, "Lock Axes")
+                            .on_hover_text("Pinned bodies stay dynamic with the checked axes locked");
+                    });
+                    if settings.pin.mode == PinMode::LockAxes {
+                        ui.horizontal(|ui| {
+                            ui.label("Translation:");
+                            for (locked, axis) in settings.pin.translation.iter_mut().zip(["X", "Y", "Z"]) {
+                                ui.checkbox(locked, axis);
+                            }
+                            ui.label("Rotation:");
+                            for (locked, axis) in settings.pin.rotation.iter_mut().zip(["X", "Y", "Z"]) {
+                                ui.checkbox(locked, axis);
+                            }
+                        });
+                    }
+                    if ui.button("Unpin All").clicked() {
+                        commands.run_system_cached(unpin_all);
+                    }
+                }
+                if interaction_mode.0 == InteractionModeType::Grab {
+                    ui.label("Grab Settings");
+                    ui.horizontal(|ui| {
+                        ui.label("Spring Compliance:");
+                        ui.add(egui::DragValue::new(&mut settings.grab.compliance).speed(0.00001).range(0.0..=0.01).max_decimals(5));
+                    });
                 }
 
                 ui.separator();
                 ui.label("Spawn Maps");
-                ui.horizontal(|ui| {
-                    for tag in [MapTag::Flat, MapTag::Ramp, MapTag::RectTank] {
-                        let label = format!("{:?}", tag);
-                        if ui.button(label).clicked() {
-                            for (entity, _) in maps.iter() {
-                                commands.entity(entity).despawn();
-                            }
-
-                            match tag {
-                                MapTag::Flat => commands.spawn((
-                                    EntityTag,
-                                    SceneRoot(
-                                    asset_server.load(
-                                        GltfAssetLabel::Scene(0)
-                                            .from_asset("maps.glb"),
-                                    )),
-                                    MapTag::Flat,
-                                    Ground,
-                                ))
-                                .observe(on_level_scene_spawn),
-                                MapTag::Ramp => commands.spawn((
-                                    EntityTag,
-                                    SceneRoot(
-                                    asset_server.load(
-                                        GltfAssetLabel::Scene(1)
-                                            .from_asset("maps.glb"),
-                                    )),
-                                    MapTag::Ramp,
-                                    Ground,
-                                ))
-                                .observe(on_level_scene_spawn),
-                                MapTag::RectTank => commands.spawn(
-                                    (SceneRoot(
-                                    asset_server.load(
-                                        GltfAssetLabel::Scene(2)
-                                            .from_asset("maps.glb"),
-                                    )),
-                                    MapTag::Ramp,
-                                    Ground,
-                                    EntityTag,
-                                ))
-                                .observe(on_level_scene_spawn),
-                            };
+                ui.horizontal_wrapped(|ui| {
+                    for name in catalog.names(SceneKind::Map) {
+                        if ui.button(name).clicked() {
+                            history.apply(EditRequest::Spawn { kind: SceneKind::Map, name: name.to_string(), transforms: vec![Transform::default()], velocity: default(), replace: true });
                         }
                     }
-                    
                 });
 
                 ui.separator();
-                ui.label("Spawn Shapes");
-                ui.horizontal(|ui| {
-                    for tag in [ShapeTag::Cube, ShapeTag::Sphere, ShapeTag::Cone, ShapeTag::Torus, ShapeTag::Cylinder, ShapeTag::SMCUBE] {
-                        let label = format!("{:?}", tag);
-                        if ui.button(label).clicked() {
-                            match tag {
-                                ShapeTag::Cube => commands.spawn((
-                                    SceneRoot(
-                                        asset_server.load(
-                                            GltfAssetLabel::Scene(1)
-                                                .from_asset("shapes.glb"),   
-                                    )),
-                                    Transform::from_xyz(0.0, 10.0, 0.0),
-                                    ShapeTag::Cube,
-                                    EntityTag
-                                ))
-                                .observe(on_shape_scene_spawn),
-                                ShapeTag::Sphere => commands.spawn((
-                                    SceneRoot(
-                                        asset_server.load(
-                                            GltfAssetLabel::Scene(4)
-                                            .from_asset("shapes.glb"),
-                                    )),
-                                    Transform::from_xyz(0.0, 10.0, 0.0),
-                                    ShapeTag::Sphere,
-                                    EntityTag,
-                                ))
-                                .observe(on_shape_scene_spawn),
-                                ShapeTag::Cone => commands.spawn((
-                                    SceneRoot(
-                                        asset_server.load(
-                                            GltfAssetLabel::Scene(0)
-                                            .from_asset("shapes.glb"),
-                                    )),
-                                    Transform::from_xyz(0.0, 10.0, 0.0),
-                                    ShapeTag::Cone,
-                                    EntityTag,
-                                ))
-                                .observe(on_shape_scene_spawn),
-                                ShapeTag::Torus => commands.spawn((
-                                    SceneRoot(
-                                        asset_server.load(
-                                            GltfAssetLabel::Scene(6)
-                                            .from_asset("shapes.glb"),
-                                        )),
-                                    Transform::from_xyz(0.0, 10.0, 0.0),
-                                    ShapeTag::Torus,
-                                    EntityTag,
-                                ))
-                                .observe(on_shape_scene_spawn),
-                                ShapeTag::Cylinder => commands.spawn((
-                                    SceneRoot(
-                                        asset_server.load(
-                                            GltfAssetLabel::Scene(2)
-                                            .from_asset("shapes.glb"),
-                                    )),
-                                    Transform::from_xyz(0.0, 10.0, 0.0),
-                                    ShapeTag::Cylinder,
-                                    EntityTag,
-                                ))
-                                .observe(on_shape_scene_spawn),
-                                ShapeTag::SMCUBE => commands.spawn((
-                                    SceneRoot(
-                                        asset_server.load(
-                                            GltfAssetLabel::Scene(3)
-                                            .from_asset("shapes.glb"),
-                                    )),
-                                    Transform::from_xyz(0.0, 10.0, 0.0),
-                                    ShapeTag::SMCUBE,
-                                    EntityTag,
-                                ))
-                                .observe(on_shape_scene_spawn)
-                            };
+                ui.label("Spawn Structures");
+                ui.horizontal_wrapped(|ui| {
+                    for name in catalog.names(SceneKind::Structure) {
+                        if ui.button(name).clicked() {
+                            history.apply(EditRequest::Spawn { kind: SceneKind::Structure, name: name.to_string(), transforms: vec![Transform::from_xyz(0.0, 0.1, 0.0)], velocity: default(), replace: true });
                         }
                     }
-
                 });
-                if ui.button("Delete Shapes").clicked() {
-                    for (entity, _) in shapes.iter() {
-                        commands.entity(entity).despawn();
-                    }
-                };
 
                 ui.separator();
-                ui.label("Spawn Structures");
-                ui.horizontal(|ui| {
-                    for tag in [StructureTag::CubeTower] {
-                        let label = format!("{:?}", tag);
-                        if ui.button(label).clicked() {
-                            for (entity, _) in structures.iter() {
-                                commands.entity(entity).despawn();
-                            }
-
-                            match tag {
-                                StructureTag::CubeTower => commands.spawn((
-                                    SceneRoot(
-                                        asset_server.load(
-                                            GltfAssetLabel::Scene(0)
-                                            .from_asset("structures.glb"),
-                                    )),
-                                    Transform::from_xyz(0.0, 0.1, 0.0),
-                                    StructureTag::CubeTower,
-                                    EntityTag,
-                                ))
-                                .observe(on_structure_scene_spawn),
-                            };
+                ui.collapsing("History", |ui| {
+                    ui.horizontal(|ui| {
+                        if ui.add_enabled(!history.history().done().is_empty(), egui::Button::new("Undo")).clicked() {
+                            history.undo();
                         }
+                        if ui.add_enabled(!history.history().undone().is_empty(), egui::Button::new("Redo")).clicked() {
+                            history.redo();
+                        }
+                    });
+                    for edit in history.history().done() {
+                        ui.label(edit.label());
+                    }
+                    // Undone edits, the next one to be redone first
+                    for edit in history.history().undone().iter().rev() {
+                        ui.label(egui::RichText::new(edit.label()).weak().italics());
                     }
                 });
             });
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn falloff_factor_at_centre_edge_and_beyond() {
+        // (profile, at the centre, at the radius)
+        let expected = [
+            (BlastFalloff::Linear, 1.0, 0.0),
+            (BlastFalloff::Quadratic, 1.0, 0.0),
+            (BlastFalloff::InverseSquare, 1.0, INVERSE_SQUARE_CORE * INVERSE_SQUARE_CORE),
+            (BlastFalloff::Constant, 1.0, 1.0),
+            (BlastFalloff::Custom([1.0, 0.9, 0.7, 0.4, 0.0]), 1.0, 0.0),
+        ];
+        for (falloff, centre, edge) in expected {
+            assert!((falloff.factor(0.0) - centre).abs() < 1e-6, "{} at the centre", falloff.label());
+            assert!((falloff.factor(1.0) - edge).abs() < 1e-6, "{} at the radius", falloff.label());
+            // Past the radius the factor stays at its edge value
+            assert!((falloff.factor(2.0) - edge).abs() < 1e-6, "{} beyond the radius", falloff.label());
+        }
+    }
+
+    #[test]
+    fn custom_falloff_interpolates_between_samples() {
+        let falloff = BlastFalloff::Custom([1.0, 0.8, 0.6, 0.4, 0.2]);
+        assert!((falloff.factor(0.125) - 0.9).abs() < 1e-6);
+        assert!((falloff.factor(0.5) - 0.6).abs() < 1e-6);
+    }
+
+    #[test]
+    fn cone_includes_bodies_up_to_the_half_angle() {
+        let cone = BlastDirection::Cone { half_angle: 30.0 };
+        let at = |degrees: f32| Quat::from_rotation_y(degrees.to_radians()) * Vec3::Z * 2.0;
+        assert_eq!(cone.push(at(0.0), Vec3::Z), Some(Vec3::Z));
+        assert_eq!(cone.push(at(29.9), Vec3::Z), Some(Vec3::Z));
+        assert_eq!(cone.push(at(30.0), Vec3::Z), Some(Vec3::Z));
+        assert_eq!(cone.push(at(30.1), Vec3::Z), None);
+        assert_eq!(cone.push(-Vec3::Z, Vec3::Z), None);
+    }
+
+    #[test]
+    fn radial_pushes_away_from_the_blast() {
+        let push = BlastDirection::Radial.push(Vec3::new(0.0, 3.0, 0.0), Vec3::Z);
+        assert_eq!(push, Some(Vec3::Y));
+    }
+
+    /// Locked state of each axis, translation x/y/z then rotation x/y/z
+    fn locked(axes: LockedAxes) -> [bool; 6] {
+        [
+            axes.is_translation_x_locked(),
+            axes.is_translation_y_locked(),
+            axes.is_translation_z_locked(),
+            axes.is_rotation_x_locked(),
+            axes.is_rotation_y_locked(),
+            axes.is_rotation_z_locked(),
+        ]
+    }
+
+    #[test]
+    fn pin_settings_lock_each_checked_axis_only() {
+        for axis in 0..6 {
+            let mut checked = [false; 6];
+            checked[axis] = true;
+            let settings = PinSettings {
+                mode: PinMode::LockAxes,
+                translation: [checked[0], checked[1], checked[2]],
+                rotation: [checked[3], checked[4], checked[5]],
+            };
+            assert_eq!(locked(settings.locked_axes()), checked, "axis {}", axis);
+        }
+    }
+
+    #[test]
+    fn pin_settings_lock_all_or_no_axes() {
+        assert_eq!(locked(PinSettings::default().locked_axes()), [true; 6]);
+        let settings = PinSettings { translation: [false; 3], rotation: [false; 3], ..default() };
+        assert_eq!(locked(settings.locked_axes()), [false; 6]);
+    }
+}
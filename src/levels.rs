@@ -70,9 +70,17 @@ pub fn level_action(
 
 /// Collection of Helper functions/components/resources to assist in Level development
 mod level_helpers {
-    use bevy::{ecs::{message::MessageReader, system::ResMut}, input::mouse::MouseWheel};
+    use bevy::{prelude::*, input::mouse::MouseWheel, state::state::FreelyMutableState};
 
-    use crate::{interactions::CursorDistance};
+    use crate::{entity_pipeline::{SceneLoadProgress, ScenePhysicsReady}, interactions::CursorDistance};
+
+    /// Tags every entity of the generic loading screen
+    #[derive(Component)]
+    pub(crate) struct OnLoadingScreen;
+
+    /// Tags the text showing how many spawned scenes are physics ready
+    #[derive(Component)]
+    pub(crate) struct LoadingProgressText;
 
     // #[derive(Resource, Default)]
     // pub(crate) struct LevelsCameraOrientation {
@@ -185,6 +193,73 @@ mod level_helpers {
     //     }
     // }
 
+    /// Spawns a loading screen (with its own camera) shown while spawned scenes get their physics applied
+    pub(crate) fn setup_loading_screen(
+        mut commands: Commands,
+        asset_server: Res<AssetServer>,
+    ) {
+        commands.spawn((
+            Camera3d::default(),
+            OnLoadingScreen,
+        ));
+        commands.spawn((
+            Text::new("Loading Level... "),
+            TextFont {
+                font: asset_server.load(r"fonts\FiraMono-Bold.ttf"),
+                font_size: 40.0,
+                ..default()
+            },
+            Node {
+                margin: UiRect::all(auto()),
+                ..default()
+            },
+            OnLoadingScreen,
+        )).with_child((
+            TextSpan::default(),
+            TextFont {
+                font: asset_server.load(r"fonts\FiraMono-Bold.ttf"),
+                font_size: 40.0,
+                ..default()
+            },
+            LoadingProgressText,
+        ));
+    }
+
+    /// Shows the amount of `TrackLoading` scenes settled by the entity pipeline out of all the ones spawned
+    pub(crate) fn track_loading_progress(
+        progress: Res<SceneLoadProgress>,
+        mut text: Single<&mut TextSpan, With<LoadingProgressText>>,
+    ) {
+        text.0 = format!("{}/{}", progress.processed + progress.failed, progress.total);
+    }
+
+    /// Moves to the `next` state once every `TrackLoading` scene is physics ready (or failed to load)
+    /// - Also leaves right away when the level spawned no tracked scene, in which case no `ScenePhysicsReady` is sent
+    pub(crate) fn finish_loading<S: FreelyMutableState + Clone>(
+        next: S,
+    ) -> impl FnMut(MessageReader<ScenePhysicsReady>, Res<SceneLoadProgress>, ResMut<NextState<S>>) {
+        move |mut ready, progress, mut next_state| {
+            if !progress.is_complete() {
+                return;
+            }
+            match ready.read().last() {
+                Some(event) => info!("{} scene(s) ready, {} failed! Leaving loading screen", event.scenes, event.failed),
+                None => info!("No scene to wait for! Leaving loading screen"),
+            }
+            next_state.set(next.clone());
+        }
+    }
+
+    /// Removes the loading screen entities
+    pub(crate) fn cleanup_loading_screen(
+        mut commands: Commands,
+        query: Query<Entity, With<OnLoadingScreen>>,
+    ) {
+        for entity in &query {
+            commands.entity(entity).despawn();
+        }
+    }
+
     pub(crate) fn scroll_control(
         mut scroll_events: MessageReader<MouseWheel>,
         mut distance: ResMut<CursorDistance>,
@@ -208,7 +283,7 @@ mod level_one {
     use avian3d::prelude::{RigidBody, Sleeping};
     use bevy::{prelude::*, time::common_conditions::on_timer};
 
-    use crate::{SimulationState, entity_pipeline::{on_level_scene_spawn, on_shape_scene_spawn}, game::ExampleViewports, levels::LevelState};
+    use crate::{SimulationState, game::ExampleViewports, levels::LevelState, scene_catalog::SceneCatalog};
 
     pub fn level_one_plugin(
         app: &mut App,
@@ -294,6 +369,7 @@ mod level_one {
     /// Additional configurations to be made upon entering Level One
     fn level_one_setup(
         mut commands: Commands,
+        catalog: Res<SceneCatalog>,
         asset_server: Res<AssetServer>,
         entity_stats: Res<EntityStats>,
     ) {
@@ -305,15 +381,7 @@ mod level_one {
             Transform::from_xyz(4.0, 10.0, 4.0),
             OnLevelOneScreen,
         ));
-        commands.spawn((
-            SceneRoot(
-                asset_server.load(
-                    GltfAssetLabel::Scene(1)
-                    .from_asset("maps.glb"),
-                )
-            ),
-            OnLevelOneScreen,
-        )).observe(on_level_scene_spawn);
+        catalog.spawn(&mut commands, "ramp-map", OnLevelOneScreen);
 
         commands.spawn((
             Text::new("Level One: Spawning Cubes"),
@@ -415,22 +483,16 @@ mod level_one {
     /// Spawns Cubes entities into the scene
     fn spawn_cubes(
         mut commands: Commands,
-        asset_server: Res<AssetServer>,
+        catalog: Res<SceneCatalog>,
         mut query: Single<&mut TextSpan, With<EntitySpawnedText>>,
         mut query_active_entities_text: Single<&mut TextSpan, (With<ActiveEntityCountSpawnedText>, Without<EntitySpawnedText>)>,
         active_entities: Query<Entity, (With<RigidBody>, Without<Sleeping>)>,
         mut entity_count: ResMut<EntityStats>,
     ) {
-        commands.spawn((
-            SceneRoot(
-                asset_server.load(
-                    GltfAssetLabel::Scene(1)
-                        .from_asset("shapes.glb"),
-                ),
-            ),
+        catalog.spawn(&mut commands, "cube", (
             Transform::from_xyz(0.0, 20.0, 0.0),
             OnLevelOneScreen,
-        )).observe(on_shape_scene_spawn);
+        ));
         entity_count.count += 1;
         let active_count = active_entities.iter().count();
         query.0 = format!("{:?}", &entity_count.count);
@@ -447,11 +509,11 @@ mod level_one {
 
 /// Impulse Force
 mod level_two {
-    use bevy::{app::{App, Update}, camera::{Camera3d, visibility::Visibility}, color::{self, Color}, ecs::{children, component::Component, entity::Entity, query::{Changed, With, Without}, schedule::{IntoScheduleConfigs, SystemCondition}, system::{Commands, Query, Res, ResMut, Single}}, gltf::GltfAssetLabel, light::PointLight, math::Vec3, prelude::SpawnRelated, scene::SceneRoot, state::{condition::in_state, state::{OnEnter, OnExit}}, text::{TextColor, TextFont, TextLayout, TextSpan}, transform::components::Transform, ui::{AlignItems, BackgroundColor, Display, FlexDirection, Interaction, JustifyContent, Node, PositionType, UiRect, Val, auto, percent, px, vh, widget::{Button, Text}}, utils::default};
+    use bevy::{app::{App, Update}, camera::{Camera3d, visibility::Visibility}, color::{self, Color}, ecs::{children, component::Component, entity::Entity, query::{Changed, With, Without}, schedule::{IntoScheduleConfigs, SystemCondition}, system::{Commands, Query, Res, ResMut, Single}}, light::PointLight, math::Vec3, prelude::SpawnRelated, scene::SceneRoot, state::{condition::in_state, state::{OnEnter, OnExit}}, text::{TextColor, TextFont, TextLayout, TextSpan}, transform::components::Transform, ui::{AlignItems, BackgroundColor, Display, FlexDirection, Interaction, JustifyContent, Node, PositionType, UiRect, Val, auto, percent, px, vh, widget::{Button, Text}}, utils::default};
     use bevy_asset::AssetServer;
     use rand::Rng;
 
-    use crate::{SimulationState, entity_pipeline::{on_level_scene_spawn, on_structure_scene_spawn}, game::ExampleViewports, interactions::{CursorDistance, ExampleLabel, ImpulseCoords, ImpulseCursor, ImpulseSettings, apply_force, draw_impulse_cursor, set_impulse_cursor_visibility}, levels::{LevelState, LevelsFlyCamera, level_helpers::scroll_control}};
+    use crate::{SimulationState, game::ExampleViewports, interactions::{BlastDirection, BlastOcclusionRays, CursorDistance, ExampleLabel, ImpulseCoords, ImpulseCursor, ImpulseSettings, apply_force, draw_blast_occlusion, draw_impulse_cursor, set_impulse_cursor_visibility}, levels::{LevelState, LevelsFlyCamera, level_helpers::scroll_control}, scene_catalog::{SceneCatalog, SceneKind}};
 
     pub fn level_two_plugin(
         app: &mut App,
@@ -459,11 +521,13 @@ mod level_two {
         app
             .add_systems(OnEnter(LevelState::TWO), (level_two_setup, initialize_cam))
             .insert_resource(ImpulseSettings::default())
+            .init_resource::<BlastOcclusionRays>()
             .insert_resource(CursorDistance(10.0))
             .add_systems(Update, (
                 scroll_control,
                 draw_impulse_cursor,
                 apply_force,
+                draw_blast_occlusion,
                 lvl_two_button_system,
                 lvl_two_action_controls,
                 track_impulse_settings,
@@ -492,6 +556,13 @@ mod level_two {
     #[derive(Component)]
     struct BlastRadiusTag;
 
+    /// Tags the blast falloff/direction texts in the UI so they can be updated
+    #[derive(Component)]
+    enum BlastModeTag {
+        Falloff,
+        Direction,
+    }
+
     /// Tag the structures spawned in Level Two to handle resetting the scene
     #[derive(Component)]
     struct Lvl2StructureTag;
@@ -503,6 +574,8 @@ mod level_two {
         BlastRadiusIncrease,
         BlastStrengthDecrease,
         BlastStrengthIncrease,
+        NextFalloff,
+        ToggleDirection,
         ResetScene,
     }
 
@@ -527,6 +600,7 @@ mod level_two {
 
     fn level_two_setup(
         mut commands: Commands,
+        catalog: Res<SceneCatalog>,
         asset_server: Res<AssetServer>,
         impulse_settings: Res<ImpulseSettings>,
     ) {
@@ -538,25 +612,18 @@ mod level_two {
             Transform::from_xyz(4.0, 10.0, 4.0),
             OnLevelTwoScreen,
         ));
-        commands.spawn((
-            SceneRoot(
-                asset_server.load(
-                    GltfAssetLabel::Scene(0)
-                    .from_asset("maps.glb"),
-                )
-            ),
-            OnLevelTwoScreen,
-        )).observe(on_level_scene_spawn);
+        catalog.spawn(&mut commands, "flat-map", OnLevelTwoScreen);
 
         let impulse_ball = commands.spawn((
-            SceneRoot(
-                asset_server.load(GltfAssetLabel::Scene(5).from_asset("shapes.glb"))
-            ),
             Transform::from_xyz(0.0, 0.0, 0.0),
             Visibility::Hidden,
             OnLevelTwoScreen,
         ))
         .insert(ImpulseCursor).id();
+        // The cursor still works without its model, `scene` already warns about the missing scene
+        if let Some(target) = catalog.scene("target") {
+            commands.entity(impulse_ball).insert(SceneRoot(target));
+        }
 
         let text_style = TextFont {
             font: asset_server.load(r"fonts\FiraMono-Medium.ttf"),
@@ -593,17 +660,13 @@ mod level_two {
         // let structure_nums: Vec<i32> = (0..3).collect();
         // let coord_nums: Vec<i32> = (0..25).collect();
         for _ in 0..5 {
-            commands.spawn((
-                SceneRoot(
-                    asset_server.load(
-                        GltfAssetLabel::Scene(rng.random_range(0..2))
-                        .from_asset("structures.glb")
-                    )
-                ),
-                Transform::from_xyz(rng.random_range(-10.0..10.0), 0.0, rng.random_range(0.0..10.0)),
-                OnLevelTwoScreen,
-                Lvl2StructureTag,
-            )).observe(on_structure_scene_spawn);
+            if let Some(name) = catalog.random(SceneKind::Structure, &mut rng) {
+                catalog.spawn(&mut commands, name, (
+                    Transform::from_xyz(rng.random_range(-10.0..10.0), 0.0, rng.random_range(0.0..10.0)),
+                    OnLevelTwoScreen,
+                    Lvl2StructureTag,
+                ));
+            }
         }
 
         // justify-content: aligns items horizontally
@@ -697,7 +760,7 @@ mod level_two {
                             Node { // row-buttons
                                 display: Display::Flex,
                                 flex_direction: FlexDirection::Row,
-                                column_gap: px(75),
+                                column_gap: px(50),
                                 top: px(10),
                                 bottom: px(20),
                                 ..default()
@@ -831,6 +894,88 @@ mod level_two {
                                         ),
                                     ]
                                 ),
+                                (
+                                    Node { // blast-falloff-buttons
+                                        display: Display::Flex,
+                                        flex_direction: FlexDirection::Column,
+                                        align_items: AlignItems::Center,
+                                        justify_content: JustifyContent::FlexStart,
+                                        ..default()
+                                    },
+                                    children![
+                                        (
+                                            Text::new("Falloff: "),
+                                            TextFont {
+                                                font: asset_server.load(r"fonts\FiraMono-Medium.ttf"),
+                                                font_size: 30.0,
+                                                ..default()
+                                            },
+                                            children![(
+                                                TextSpan::new(impulse_settings.falloff.label()),
+                                                TextFont {
+                                                    font: asset_server.load(r"fonts\FiraMono-Medium.ttf"),
+                                                    font_size: 30.0,
+                                                    ..default()
+                                                },
+                                                BlastModeTag::Falloff,
+                                            )]
+                                        ),
+                                        (
+                                            Button,
+                                            button_node.clone(),
+                                            BackgroundColor(NORMAL_BUTTON),
+                                            BlastControlsButtonAction::NextFalloff,
+                                            children![
+                                                (
+                                                    Text::new(">"),
+                                                    button_text_font.clone(),
+                                                    TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                                                )
+                                            ]
+                                        )
+                                    ]
+                                ),
+                                (
+                                    Node { // blast-direction-buttons
+                                        display: Display::Flex,
+                                        flex_direction: FlexDirection::Column,
+                                        align_items: AlignItems::Center,
+                                        justify_content: JustifyContent::FlexStart,
+                                        ..default()
+                                    },
+                                    children![
+                                        (
+                                            Text::new("Blast: "),
+                                            TextFont {
+                                                font: asset_server.load(r"fonts\FiraMono-Medium.ttf"),
+                                                font_size: 30.0,
+                                                ..default()
+                                            },
+                                            children![(
+                                                TextSpan::new(impulse_settings.direction.label()),
+                                                TextFont {
+                                                    font: asset_server.load(r"fonts\FiraMono-Medium.ttf"),
+                                                    font_size: 30.0,
+                                                    ..default()
+                                                },
+                                                BlastModeTag::Direction,
+                                            )]
+                                        ),
+                                        (
+                                            Button,
+                                            button_node.clone(),
+                                            BackgroundColor(NORMAL_BUTTON),
+                                            BlastControlsButtonAction::ToggleDirection,
+                                            children![
+                                                (
+                                                    Text::new(">"),
+                                                    button_text_font.clone(),
+                                                    TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                                                )
+                                            ]
+                                        )
+                                    ]
+                                ),
                                 (
                                     Node { // reset-scene-buttons
                                         display: Display::Flex,
@@ -887,7 +1032,7 @@ mod level_two {
     /// Manages controls to update the value of blast_radius and blast_strength and support resetting the scene
     fn lvl_two_action_controls(
         mut commands: Commands,
-        asset_server: Res<AssetServer>,
+        catalog: Res<SceneCatalog>,
         interaction_query: Query<(&Interaction, &mut BlastControlsButtonAction), (Changed<Interaction>, With<Button>)>,
         mut impulse_settings: ResMut<ImpulseSettings>,
         structure_query: Query<Entity, With<Lvl2StructureTag>>,
@@ -907,6 +1052,15 @@ mod level_two {
                     BlastControlsButtonAction::BlastStrengthIncrease => {
                         impulse_settings.max_force += 5.0;
                     }
+                    BlastControlsButtonAction::NextFalloff => {
+                        impulse_settings.falloff = impulse_settings.falloff.next();
+                    }
+                    BlastControlsButtonAction::ToggleDirection => {
+                        impulse_settings.direction = match impulse_settings.direction {
+                            BlastDirection::Radial => BlastDirection::DEFAULT_CONE,
+                            BlastDirection::Cone { .. } => BlastDirection::Radial,
+                        };
+                    }
                     BlastControlsButtonAction::ResetScene => {
                         // Delete all structures in the level
                         for entity in &structure_query {
@@ -916,17 +1070,13 @@ mod level_two {
                         // run same logic to respawn all structures in random locations
                         let mut rng = rand::rng();
                         for _ in 0..5 {
-                            commands.spawn((
-                                SceneRoot(
-                                    asset_server.load(
-                                        GltfAssetLabel::Scene(rng.random_range(0..2))
-                                        .from_asset("structures.glb")
-                                    )
-                                ),
-                                Transform::from_xyz(rng.random_range(-10.0..10.0), 0.0, rng.random_range(0.0..10.0)),
-                                OnLevelTwoScreen,
-                                Lvl2StructureTag,
-                            )).observe(on_structure_scene_spawn);
+                            if let Some(name) = catalog.random(SceneKind::Structure, &mut rng) {
+                                catalog.spawn(&mut commands, name, (
+                                    Transform::from_xyz(rng.random_range(-10.0..10.0), 0.0, rng.random_range(0.0..10.0)),
+                                    OnLevelTwoScreen,
+                                    Lvl2StructureTag,
+                                ));
+                            }
                         }
                     }
                 }
@@ -934,14 +1084,25 @@ mod level_two {
         }
     }
 
-    /// Used to update the value of blast_radius and blast_strength in the UI
+    type BlastRadiusText = (With<BlastRadiusTag>, Without<BlastStrengthTag>, Without<BlastModeTag>);
+    type BlastStrengthText = (With<BlastStrengthTag>, Without<BlastRadiusTag>, Without<BlastModeTag>);
+    type BlastModeText = (Without<BlastRadiusTag>, Without<BlastStrengthTag>);
+
+    /// Used to update the value of blast_radius, blast_strength and the blast falloff/direction in the UI
     fn track_impulse_settings(
-        mut blast_radius_text_query: Single<&mut TextSpan, (With<BlastRadiusTag>, Without<BlastStrengthTag>)>,
-        mut blast_strength_text_query: Single<&mut TextSpan, (With<BlastStrengthTag>, Without<BlastRadiusTag>)>,
+        mut blast_radius_text_query: Single<&mut TextSpan, BlastRadiusText>,
+        mut blast_strength_text_query: Single<&mut TextSpan, BlastStrengthText>,
+        mut blast_mode_text_query: Query<(&mut TextSpan, &BlastModeTag), BlastModeText>,
         impulse_settings: Res<ImpulseSettings>,
     ) {
         blast_radius_text_query.0 = format!("{:?}", impulse_settings.blast_radius);
         blast_strength_text_query.0 = format!("{:?}", impulse_settings.max_force);
+        for (mut text, tag) in &mut blast_mode_text_query {
+            text.0 = match tag {
+                BlastModeTag::Falloff => impulse_settings.falloff.label(),
+                BlastModeTag::Direction => impulse_settings.direction.label(),
+            }.to_string();
+        }
     }
 
     fn level_two_cleanup(
@@ -949,8 +1110,7 @@ mod level_two {
         query: Query<Entity, With<OnLevelTwoScreen>>,
         mut impulse_settings: ResMut<ImpulseSettings>,
     ) {
-        impulse_settings.blast_radius = 50.0;
-        impulse_settings.max_force = 100.0;
+        *impulse_settings = ImpulseSettings::default();
         for entity in &query {
             commands.entity(entity).despawn();
         }
@@ -959,11 +1119,11 @@ mod level_two {
 
 /// Wrecker Ball
 mod level_three {
-    use avian3d::prelude::{Collider, RigidBody};
+    use avian3d::prelude::{Collider, RigidBody, SweptCcd};
     use bevy::{color, prelude::*};
     use rand::Rng;
 
-    use crate::{SimulationState, entity_pipeline::{on_level_scene_spawn, on_structure_scene_spawn}, game::ExampleViewports, interactions::{CursorDistance, ExampleLabel, WreckerCoords, WreckerCursor, center_cursor, draw_wrecker_cursor, set_wrecker_cursor_visibility}, levels::{LevelState, LevelsFlyCamera, level_helpers::scroll_control}};
+    use crate::{SimulationState, game::ExampleViewports, interactions::{CursorDistance, ExampleLabel, WreckerCoords, WreckerCursor, WreckerSettings, WreckingBallSettings, center_cursor, despawn_wrecking_ball, draw_wrecker_cursor, draw_wrecking_ball_chain, set_wrecker_cursor_visibility, spawn_wrecking_ball, update_wrecking_ball}, levels::{LevelState, LevelsFlyCamera, level_helpers::scroll_control}, scene_catalog::{SceneCatalog, SceneKind}};
     
     pub fn level_three_plugin(
         app: &mut App,
@@ -971,6 +1131,9 @@ mod level_three {
         app
             .add_systems(OnEnter(LevelState::THREE), (level_three_setup, initialize_cam))
             .insert_resource(CursorDistance(10.0))
+            .insert_resource(WreckingBallSettings::default())
+            .insert_resource(WreckerSettings::default())
+            .insert_resource(ChainedWrecker(false))
             .add_systems(Update, (
                 scroll_control,
                 draw_wrecker_cursor,
@@ -978,8 +1141,10 @@ mod level_three {
                 lvl_three_action_controls,
                 track_wrecker_settings,
                 set_wrecker_cursor_visibility::<true>,
+                (spawn_wrecking_ball, update_wrecking_ball, draw_wrecking_ball_chain).chain().run_if(resource_equals(ChainedWrecker(true))),
+                despawn_wrecking_ball.run_if(resource_equals(ChainedWrecker(false))),
             ).run_if(in_state(LevelState::THREE)).run_if(in_state(SimulationState::Running).or(in_state(SimulationState::Paused))))
-            .add_systems(OnExit(LevelState::THREE), (level_three_cleanup, center_cursor));
+            .add_systems(OnExit(LevelState::THREE), (level_three_cleanup, despawn_wrecking_ball, center_cursor));
     }
 
     #[derive(Component)]
@@ -998,10 +1163,27 @@ mod level_three {
     #[derive(Component)]
     struct WreckerBallScaleTag;
 
+    /// Tags the wrecking ball texts in the UI so they can be updated
+    #[derive(Component)]
+    enum WreckingBallTag {
+        Chain,
+        ChainLength,
+        BallMass,
+    }
+
+    /// Whether a wrecking ball hangs from the wrecker on a chain
+    #[derive(Resource, PartialEq)]
+    struct ChainedWrecker(bool);
+
     #[derive(Component)]
     enum WreckerControlsBunttonAction {
         WreckerScaleDecrease,
         WreckerScaleIncrease,
+        ToggleChain,
+        ChainLengthDecrease,
+        ChainLengthIncrease,
+        BallMassDecrease,
+        BallMassIncrease,
         ResetScene,
     }
 
@@ -1025,9 +1207,11 @@ mod level_three {
 
     fn level_three_setup(
         mut commands: Commands,
+        catalog: Res<SceneCatalog>,
         mut meshes: ResMut<Assets<Mesh>>,
         mut materials: ResMut<Assets<StandardMaterial>>,
         asset_server: Res<AssetServer>,
+        wrecking_ball_settings: Res<WreckingBallSettings>,
     ) {
         commands.spawn((
             PointLight {
@@ -1037,31 +1221,19 @@ mod level_three {
             Transform::from_xyz(4.0, 10.0, 4.0),
             OnLevelThreeScreen,
         ));
-        commands.spawn((
-            SceneRoot(
-                asset_server.load(
-                    GltfAssetLabel::Scene(0)
-                    .from_asset("maps.glb"),
-                )
-            ),
-            OnLevelThreeScreen,
-        )).observe(on_level_scene_spawn);
+        catalog.spawn(&mut commands, "flat-map", OnLevelThreeScreen);
 
         let mut rng = rand::rng();
         // let structure_nums: Vec<i32> = (0..3).collect();
         // let coord_nums: Vec<i32> = (0..25).collect();
         for _ in 0..5 {
-            commands.spawn((
-                SceneRoot(
-                    asset_server.load(
-                        GltfAssetLabel::Scene(rng.random_range(0..2))
-                        .from_asset("structures.glb")
-                    )
-                ),
-                Transform::from_xyz(rng.random_range(-10.0..10.0), 0.0, rng.random_range(0.0..10.0)),
-                OnLevelThreeScreen,
-                Lvl3StructureTag,
-            )).observe(on_structure_scene_spawn);
+            if let Some(name) = catalog.random(SceneKind::Structure, &mut rng) {
+                catalog.spawn(&mut commands, name, (
+                    Transform::from_xyz(rng.random_range(-10.0..10.0), 0.0, rng.random_range(0.0..10.0)),
+                    OnLevelThreeScreen,
+                    Lvl3StructureTag,
+                ));
+            }
         }
 
         let text_style = TextFont {
@@ -1076,6 +1248,7 @@ mod level_three {
             Transform::from_xyz(0.0, 10.0, 0.0),
             Collider::sphere(0.5),
             RigidBody::Kinematic,
+            SweptCcd::default(),
             Visibility::Hidden,
             OnLevelThreeScreen,
         ))
@@ -1195,7 +1368,7 @@ mod level_three {
                             Node { // row-buttons
                                 display: Display::Flex,
                                 flex_direction: FlexDirection::Row,
-                                column_gap: px(75),
+                                column_gap: px(40),
                                 top: px(10),
                                 bottom: px(20),
                                 ..default()
@@ -1265,6 +1438,175 @@ mod level_three {
                                         ),
                                     ]
                                 ),
+                                (
+                                    Node { // chain-toggle
+                                        display: Display::Flex,
+                                        flex_direction: FlexDirection::Column,
+                                        align_items: AlignItems::Center,
+                                        justify_content: JustifyContent::Center,
+                                        ..default()
+                                    },
+                                    children![
+                                        (
+                                            Text::new("Chain: "),
+                                            TextFont {
+                                                font: asset_server.load(r"fonts\FiraMono-Medium.ttf"),
+                                                font_size: 30.0,
+                                                ..default()
+                                            },
+                                            children![(
+                                                TextSpan::new("Off"),
+                                                TextFont {
+                                                    font: asset_server.load(r"fonts\FiraMono-Medium.ttf"),
+                                                    font_size: 30.0,
+                                                    ..default()
+                                                },
+                                                WreckingBallTag::Chain,
+                                            )]
+                                        ),
+                                        (
+                                            Button,
+                                            button_node.clone(),
+                                            BackgroundColor(NORMAL_BUTTON),
+                                            WreckerControlsBunttonAction::ToggleChain,
+                                            children![
+                                                (
+                                                    Text::new(">"),
+                                                    button_text_font.clone(),
+                                                    TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                                                )
+                                            ]
+                                        )
+                                    ]
+                                ),
+                                (
+                                   Node { // chain-length-buttons
+                                    display: Display::Flex,
+                                    flex_direction: FlexDirection::Column,
+                                    align_items: AlignItems::Center,
+                                    justify_content: JustifyContent::Center,
+                                    ..default()
+                                   },
+                                   children![
+                                        (
+                                            Text::new("Chain Length: "),
+                                            TextFont {
+                                                font: asset_server.load(r"fonts\FiraMono-Medium.ttf"),
+                                                font_size: 30.0,
+                                                ..default()
+                                            },
+                                            children![(
+                                                TextSpan::new(format!("{:.1}", wrecking_ball_settings.chain_length)),
+                                                TextFont {
+                                                    font: asset_server.load(r"fonts\FiraMono-Medium.ttf"),
+                                                    font_size: 30.0,
+                                                    ..default()
+                                                },
+                                                WreckingBallTag::ChainLength,
+                                            )]
+                                        ),
+                                        (
+                                            Node { // chain-length-buttons-row
+                                                display: Display::Flex,
+                                                flex_direction: FlexDirection::Row,
+                                                column_gap: px(5.0),
+                                                ..default()
+                                            },
+                                            children![
+                                                (
+                                                    Button,
+                                                    button_node.clone(),
+                                                    BackgroundColor(NORMAL_BUTTON),
+                                                    WreckerControlsBunttonAction::ChainLengthDecrease,
+                                                    children![
+                                                        (
+                                                            Text::new("-"),
+                                                            button_text_font.clone(),
+                                                            TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                                                        )
+                                                    ]
+                                                ),
+                                                (
+                                                    Button,
+                                                    button_node.clone(),
+                                                    BackgroundColor(NORMAL_BUTTON),
+                                                    WreckerControlsBunttonAction::ChainLengthIncrease,
+                                                    children![
+                                                        (
+                                                            Text::new("+"),
+                                                            button_text_font.clone(),
+                                                            TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                                                        )
+                                                    ]
+                                                ),
+                                            ],
+                                        ),
+                                    ]
+                                ),
+                                (
+                                   Node { // ball-mass-buttons
+                                    display: Display::Flex,
+                                    flex_direction: FlexDirection::Column,
+                                    align_items: AlignItems::Center,
+                                    justify_content: JustifyContent::Center,
+                                    ..default()
+                                   },
+                                   children![
+                                        (
+                                            Text::new("Ball Mass: "),
+                                            TextFont {
+                                                font: asset_server.load(r"fonts\FiraMono-Medium.ttf"),
+                                                font_size: 30.0,
+                                                ..default()
+                                            },
+                                            children![(
+                                                TextSpan::new(format!("{:.0}", wrecking_ball_settings.ball_mass)),
+                                                TextFont {
+                                                    font: asset_server.load(r"fonts\FiraMono-Medium.ttf"),
+                                                    font_size: 30.0,
+                                                    ..default()
+                                                },
+                                                WreckingBallTag::BallMass,
+                                            )]
+                                        ),
+                                        (
+                                            Node { // ball-mass-buttons-row
+                                                display: Display::Flex,
+                                                flex_direction: FlexDirection::Row,
+                                                column_gap: px(5.0),
+                                                ..default()
+                                            },
+                                            children![
+                                                (
+                                                    Button,
+                                                    button_node.clone(),
+                                                    BackgroundColor(NORMAL_BUTTON),
+                                                    WreckerControlsBunttonAction::BallMassDecrease,
+                                                    children![
+                                                        (
+                                                            Text::new("-"),
+                                                            button_text_font.clone(),
+                                                            TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                                                        )
+                                                    ]
+                                                ),
+                                                (
+                                                    Button,
+                                                    button_node.clone(),
+                                                    BackgroundColor(NORMAL_BUTTON),
+                                                    WreckerControlsBunttonAction::BallMassIncrease,
+                                                    children![
+                                                        (
+                                                            Text::new("+"),
+                                                            button_text_font.clone(),
+                                                            TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                                                        )
+                                                    ]
+                                                ),
+                                            ],
+                                        ),
+                                    ]
+                                ),
                                 (
                                     Node { // reset-scene
                                         display: Display::Flex,
@@ -1318,13 +1660,15 @@ mod level_three {
         }
     }
 
-    /// Manages controls to update the value of the wrecker ball scale and support resetting the scene
+    /// Manages controls to update the value of the wrecker ball scale, the wrecking ball chain and support resetting the scene
     fn lvl_three_action_controls(
         mut commands: Commands,
-        asset_server: Res<AssetServer>,
+        catalog: Res<SceneCatalog>,
         interaction_query: Query<(&Interaction, &mut WreckerControlsBunttonAction), (Changed<Interaction>, With<Button>)>,
         mut wrecker_query: Single<&mut Transform, With<WreckerCursor>>,
         structure_query: Query<Entity, With<Lvl3StructureTag>>,
+        mut chained_wrecker: ResMut<ChainedWrecker>,
+        mut wrecking_ball_settings: ResMut<WreckingBallSettings>,
     ) {
         for (interaction, wrecker_controls_button_action) in &interaction_query {
             if *interaction == Interaction::Pressed {
@@ -1338,6 +1682,21 @@ mod level_three {
                     WreckerControlsBunttonAction::WreckerScaleIncrease => {
                         wrecker_query.scale += 1.0;
                     }
+                    WreckerControlsBunttonAction::ToggleChain => {
+                        chained_wrecker.0 = !chained_wrecker.0;
+                    }
+                    WreckerControlsBunttonAction::ChainLengthDecrease => {
+                        wrecking_ball_settings.chain_length = (wrecking_ball_settings.chain_length - 1.0).max(1.0);
+                    }
+                    WreckerControlsBunttonAction::ChainLengthIncrease => {
+                        wrecking_ball_settings.chain_length += 1.0;
+                    }
+                    WreckerControlsBunttonAction::BallMassDecrease => {
+                        wrecking_ball_settings.ball_mass = (wrecking_ball_settings.ball_mass - 10.0).max(10.0);
+                    }
+                    WreckerControlsBunttonAction::BallMassIncrease => {
+                        wrecking_ball_settings.ball_mass += 10.0;
+                    }
                     WreckerControlsBunttonAction::ResetScene => {
                         // Delete all structures in the level
                         for entity in &structure_query {
@@ -1347,17 +1706,13 @@ mod level_three {
                         // run same logic to respawn all structures in random locations
                         let mut rng = rand::rng();
                         for _ in 0..5 {
-                            commands.spawn((
-                                SceneRoot(
-                                    asset_server.load(
-                                        GltfAssetLabel::Scene(rng.random_range(0..2))
-                                        .from_asset("structures.glb")
-                                    )
-                                ),
-                                Transform::from_xyz(rng.random_range(-10.0..10.0), 0.0, rng.random_range(0.0..10.0)),
-                                OnLevelThreeScreen,
-                                Lvl3StructureTag,
-                            )).observe(on_structure_scene_spawn);
+                            if let Some(name) = catalog.random(SceneKind::Structure, &mut rng) {
+                                catalog.spawn(&mut commands, name, (
+                                    Transform::from_xyz(rng.random_range(-10.0..10.0), 0.0, rng.random_range(0.0..10.0)),
+                                    OnLevelThreeScreen,
+                                    Lvl3StructureTag,
+                                ));
+                            }
                         }
                     }
                 }
@@ -1367,18 +1722,32 @@ mod level_three {
 
     fn track_wrecker_settings(
         wrecker_query: Single<&Transform, With<WreckerCursor>>,
-        mut wrecker_scale_text_query: Single<&mut TextSpan, With<WreckerBallScaleTag>>,
+        mut wrecker_scale_text_query: Single<&mut TextSpan, (With<WreckerBallScaleTag>, Without<WreckingBallTag>)>,
+        mut wrecking_ball_text_query: Query<(&mut TextSpan, &WreckingBallTag), Without<WreckerBallScaleTag>>,
+        chained_wrecker: Res<ChainedWrecker>,
+        wrecking_ball_settings: Res<WreckingBallSettings>,
     ) {
         wrecker_scale_text_query.0 = format!("{:.1}", wrecker_query.scale.x);
+        for (mut text, tag) in &mut wrecking_ball_text_query {
+            text.0 = match tag {
+                WreckingBallTag::Chain => if chained_wrecker.0 { "On".to_string() } else { "Off".to_string() },
+                WreckingBallTag::ChainLength => format!("{:.1}", wrecking_ball_settings.chain_length),
+                WreckingBallTag::BallMass => format!("{:.0}", wrecking_ball_settings.ball_mass),
+            };
+        }
     }
 
     fn level_three_cleanup(
         mut commands: Commands,
         query: Query<Entity, With<OnLevelThreeScreen>>,
+        mut chained_wrecker: ResMut<ChainedWrecker>,
+        mut wrecking_ball_settings: ResMut<WreckingBallSettings>,
     ) {
         for entity in &query {
             commands.entity(entity).despawn();
         }
+        chained_wrecker.0 = false;
+        *wrecking_ball_settings = WreckingBallSettings::default();
     }
 }
 
@@ -1386,13 +1755,13 @@ mod level_three {
 mod level_four {
     use std::time::Duration;
 
-    use avian3d::prelude::{Collider, ConstantForce, Mass, RigidBody};
+    use avian3d::prelude::{Collider, ConstantForce, Mass, RigidBody, SweptCcd};
     use bevy::{color, prelude::*, time::common_conditions::on_timer};
     use bevy_asset::{AssetServer, Assets};
     use rand::Rng;
     use strum::EnumIter;
 
-    use crate::{SimulationState, entity_pipeline::{StructureBlock, on_level_scene_spawn, on_structure_scene_spawn}, game::ExampleViewports, interactions::center_cursor, levels::LevelState};
+    use crate::{SimulationState, entity_pipeline::{StructureBlock, TrackLoading}, game::ExampleViewports, interactions::center_cursor, levels::{LevelState, level_helpers::{cleanup_loading_screen, finish_loading, setup_loading_screen, track_loading_progress}}, scene_catalog::{SceneCatalog, SceneKind}};
 
     
     pub fn level_four_plugin(
@@ -1401,8 +1770,11 @@ mod level_four {
         app
             .init_state::<LevelFourState>()
             .add_systems(OnEnter(LevelState::FOUR), level_four_setup)
-            .add_systems(OnEnter(LevelFourState::Loading), setup_delay_timer)
-            .add_systems(Update, check_delay_timer.run_if(in_state(LevelFourState::Loading)))
+            .add_systems(OnEnter(LevelFourState::Loading), setup_loading_screen)
+            .add_systems(Update, (
+                track_loading_progress,
+                finish_loading(LevelFourState::Running),
+            ).run_if(in_state(LevelFourState::Loading)))
             .add_systems(OnEnter(LevelFourState::Running), (initialize_cam, cleanup_loading_screen, level_four_text))
             .add_systems(Update, (
                 rotate_level_four_cam,
@@ -1411,7 +1783,7 @@ mod level_four {
                 lvl_four_button_system,
                 lvl_four_action_controls
             ).run_if(in_state(LevelFourState::Running)).run_if(in_state(LevelState::FOUR)).run_if(in_state(SimulationState::Running).or(in_state(SimulationState::Paused))))
-            .add_systems(OnExit(LevelState::FOUR), (level_four_cleanup, center_cursor));
+            .add_systems(OnExit(LevelState::FOUR), (level_four_cleanup, cleanup_loading_screen, center_cursor));
     }
 
     /// Keeps track of internal states inside of Level 4
@@ -1423,14 +1795,6 @@ mod level_four {
         Running,
     }
 
-    /// Used to delay the execution of the level until all entities are loaded
-    #[derive(Component)]
-    struct DelayTimer(Timer);
-
-    /// Tags entities loaded in Level four loading state
-    #[derive(Component)]
-    struct OnLevelFourLoadingScreen;
-
     /// Tags the primary 3D camera in Level Four
     #[derive(Component)]
     struct LevelFourCamera;
@@ -1501,7 +1865,7 @@ mod level_four {
     /// Loads all entities in Level four
     fn level_four_setup(
         mut commands: Commands,
-        asset_server: Res<AssetServer>,
+        catalog: Res<SceneCatalog>,
         mut next_level_four_state: ResMut<NextState<LevelFourState>>,
     ) {
         commands.spawn((
@@ -1512,29 +1876,18 @@ mod level_four {
             Transform::from_xyz(4.0, 10.0, 4.0),
             OnLevelFourScreen,
         ));
-        commands.spawn((
-            SceneRoot(
-                asset_server.load(
-                    GltfAssetLabel::Scene(0)
-                    .from_asset("maps.glb"),
-                )
-            ),
-            OnLevelFourScreen,
-        )).observe(on_level_scene_spawn);
+        catalog.spawn(&mut commands, "flat-map", (OnLevelFourScreen, TrackLoading));
 
         let mut rng = rand::rng();
         for _ in 0..75 {
-            commands.spawn((
-                SceneRoot(
-                    asset_server.load(
-                        GltfAssetLabel::Scene(rng.random_range(0..2))
-                        .from_asset("structures.glb")
-                    )
-                ),
-                Transform::from_xyz(rng.random_range(-50.0..50.0), 0.0, rng.random_range(-50.0..50.0)),
-                OnLevelFourScreen,
-                Lvl4StructureTag,
-            )).observe(on_structure_scene_spawn);
+            if let Some(name) = catalog.random(SceneKind::Structure, &mut rng) {
+                catalog.spawn(&mut commands, name, (
+                    Transform::from_xyz(rng.random_range(-50.0..50.0), 0.0, rng.random_range(-50.0..50.0)),
+                    OnLevelFourScreen,
+                    Lvl4StructureTag,
+                    TrackLoading,
+                ));
+            }
         }
         next_level_four_state.set(LevelFourState::Loading);
     }
@@ -1692,7 +2045,7 @@ mod level_four {
     /// Manages controls to update the Asteroids scene based on button pressed
     fn lvl_four_action_controls(
         mut commands: Commands,
-        asset_server: Res<AssetServer>,
+        catalog: Res<SceneCatalog>,
         mut next_level_four_state: ResMut<NextState<LevelFourState>>,
         interaction_query: Query<(&Interaction, &mut AsteroidsButtonControls), (Changed<Interaction>, With<Button>)>,
         structure_query: Query<Entity, Or<(With<Lvl4StructureTag>, With<LevelFourCamera>)>>,
@@ -1706,17 +2059,14 @@ mod level_four {
                         }
                         let mut rng = rand::rng();
                         for _ in 0..75 {
-                            commands.spawn((
-                                SceneRoot(
-                                    asset_server.load(
-                                        GltfAssetLabel::Scene(rng.random_range(0..2))
-                                        .from_asset("structures.glb")
-                                    )
-                                ),
-                                Transform::from_xyz(rng.random_range(-50.0..50.0), 0.0, rng.random_range(-50.0..50.0)),
-                                OnLevelFourScreen,
-                                Lvl4StructureTag,
-                            )).observe(on_structure_scene_spawn);
+                            if let Some(name) = catalog.random(SceneKind::Structure, &mut rng) {
+                                catalog.spawn(&mut commands, name, (
+                                    Transform::from_xyz(rng.random_range(-50.0..50.0), 0.0, rng.random_range(-50.0..50.0)),
+                                    OnLevelFourScreen,
+                                    Lvl4StructureTag,
+                                    TrackLoading,
+                                ));
+                            }
                         }
                         next_level_four_state.set(LevelFourState::Loading);
                     }
@@ -1725,53 +2075,6 @@ mod level_four {
         }
     }
 
-    /// Creates a timer preventing execution of level four until entities are done loading (4s)
-    fn setup_delay_timer(
-        mut commands: Commands,
-        asset_server: Res<AssetServer>,
-    ) {
-        commands.spawn((
-            Camera3d::default(),
-            OnLevelFourLoadingScreen,
-            OnLevelFourScreen,
-        ));
-        commands.spawn((
-            Text::new("Loading Level..."),
-            TextFont {
-                font: asset_server.load(r"fonts\FiraMono-Bold.ttf"),
-                font_size: 40.0,
-                ..default()
-            },
-            Node {
-                margin: UiRect::all(auto()),
-                ..default()
-            },
-            OnLevelFourScreen,
-            OnLevelFourLoadingScreen,
-        ));
-        let mut timer = Timer::new(Duration::from_secs(4), TimerMode::Once);
-        timer.tick(Duration::from_secs_f32(0.01));
-        commands.spawn((DelayTimer(timer), OnLevelFourLoadingScreen));
-    }
-
-    /// Continuously checks Delay Timer until it is done with execution then transitons Level four state
-    fn check_delay_timer(
-        time: Res<Time>,
-        mut commands: Commands,
-        mut query: Query<(Entity, &mut DelayTimer)>,
-        mut next_level_four_state: ResMut<NextState<LevelFourState>>,
-    ) {
-        for (entity, mut delay_timer) in &mut query {
-            delay_timer.0.tick(time.delta());
-
-            if delay_timer.0.is_finished() {
-                info!("Delay finished! Moving to Running state");
-                next_level_four_state.set(LevelFourState::Running);
-                commands.entity(entity).despawn();
-            }
-        }
-    }
-
     /// Spawns Asteroid entities into the scene
     fn spawn_asteroids(
         mut commands: Commands,
@@ -1789,6 +2092,8 @@ mod level_four {
             Mass(500.0),
             ConstantForce::new(5000.0, 0.0, 0.0),
             RigidBody::Dynamic,
+            // Asteroids get fast enough to tunnel through the blocks
+            SweptCcd::default(),
             AsteroidTag,
             OnLevelFourScreen,
             Lvl4StructureTag,
@@ -1808,16 +2113,6 @@ mod level_four {
         }
     }
 
-    /// Cleans the loading screen entities when transitioning to running state
-    fn cleanup_loading_screen(
-        mut commands: Commands,
-        query: Query<Entity, With<OnLevelFourLoadingScreen>>,
-    ) {
-        for entity in &query {
-            commands.entity(entity).despawn();
-        }
-    }
-
     /// Cleans all entities when level 4 
     fn level_four_cleanup(
         mut commands: Commands,
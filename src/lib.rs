@@ -0,0 +1,3 @@
+//! Shared code that doesn't need the game itself, used by the `lint_extras` tool and the game binary
+
+pub mod extras;
@@ -1,8 +1,13 @@
 mod entity_pipeline;
 mod game;
+mod history;
+mod inspector;
 mod interactions;
 mod levels;
 mod menus;
+mod scene_catalog;
+mod spawner;
+mod transform_gizmo;
 
 use avian3d::{PhysicsPlugins, prelude::*};
 use bevy::{DefaultPlugins, diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin}, prelude::*};
@@ -63,7 +68,7 @@ fn main() {
         .add_systems(Startup, fps_text)
         .add_systems(Update, (log_state_changes, set_max_fps, fps_counter))
         .add_systems(OnEnter(GameState::Menu), setup)
-        .add_plugins((menus::main_menu::menu_plugin, game::game_plugin, menus::pause_menu::pause_menu_plugin, levels::levels_plugin))
+        .add_plugins((menus::main_menu::menu_plugin, game::game_plugin, menus::pause_menu::pause_menu_plugin, levels::levels_plugin, entity_pipeline::entity_pipeline_plugin, scene_catalog::scene_catalog_plugin, inspector::inspector_plugin, history::history_plugin, transform_gizmo::transform_gizmo_plugin, spawner::spawner_plugin))
         .add_systems(OnEnter(GameState::Menu), cleanup_entities)
         .add_systems(OnExit(GameState::Menu), cleanup_setup)
         .run();
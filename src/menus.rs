@@ -4,6 +4,7 @@ pub mod main_menu {
     use crate::SetFps;
 
     use crate::GameState;
+    use crate::scene_catalog::SceneCatalog;
 
     // This plugin manages the menu
     pub fn menu_plugin(
@@ -28,7 +29,7 @@ pub mod main_menu {
             // Common systems to all screens that handles buttons behavior
             .add_systems(
                 Update,
-                (menu_action, button_system).run_if(in_state(GameState::Menu)),
+                (menu_action, button_system, show_scene_loading).run_if(in_state(GameState::Menu)),
             )
             .add_systems(OnEnter(MenuState::Settings), in_game_settings_menu_setup)
             .add_systems(Update, setting_button::<SetFps>.run_if(in_state(MenuState::Settings)));
@@ -51,6 +52,10 @@ pub mod main_menu {
     #[derive(Component)]
     struct OnSettingsMenuScreen;
 
+    /// Tags the "Loading scenes..." text of the main menu, shown until the scene catalog is ready
+    #[derive(Component)]
+    struct SceneLoadingText;
+
     const NORMAL_BUTTON: Color = Color::srgb(0.15, 0.15, 0.15);
     const HOVERED_BUTTON: Color = Color::srgb(0.25, 0.25, 0.25);
     const HOVERED_PRESSED_BUTTON: Color = Color::srgb(0.25, 0.65, 0.25);
@@ -106,6 +111,16 @@ pub mod main_menu {
         }
     }
 
+    /// Shows the "Loading scenes..." text while the scene catalog is still loading
+    fn show_scene_loading(
+        catalog: Res<SceneCatalog>,
+        mut texts: Query<&mut Visibility, With<SceneLoadingText>>,
+    ) {
+        for mut visibility in &mut texts {
+            visibility.set_if_neq(if catalog.is_ready() { Visibility::Hidden } else { Visibility::Inherited });
+        }
+    }
+
     fn menu_setup(mut menu_state: ResMut<NextState<MenuState>>) {
         menu_state.set(MenuState::Main);
     }
@@ -172,6 +187,15 @@ pub mod main_menu {
                             ..default()
                         },
                     ),
+                    (
+                        Text::new("Loading scenes..."),
+                        TextFont {
+                            font_size: 25.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                        SceneLoadingText,
+                    ),
                     // Display all buttons for each action available from the main menu
                     (
                         Button,
@@ -332,13 +356,27 @@ pub mod main_menu {
         mut app_exit_writer: MessageWriter<AppExit>,
         mut menu_state: ResMut<NextState<MenuState>>,
         mut game_state: ResMut<NextState<GameState>>,
+        catalog: Res<SceneCatalog>,
+        mut pending: Local<Option<GameState>>,
     ) {
+        // Both the playground and the levels spawn their scenes on enter, which needs the scene catalog to be filled in,
+        // so a click before that is remembered and followed once the catalog is ready
+        if let Some(state) = *pending
+            && catalog.is_ready()
+        {
+            *pending = None;
+            game_state.set(state);
+            menu_state.set(MenuState::Disabled);
+        }
         for (interaction, menu_button_action) in &interaction_query {
             if *interaction == Interaction::Pressed {
                 match menu_button_action {
                     MenuButtonAction::Quit => {
                         app_exit_writer.write(AppExit::Success);
                     }
+                    MenuButtonAction::Playground | MenuButtonAction::Levels if !catalog.is_ready() => {
+                        *pending = Some(if matches!(menu_button_action, MenuButtonAction::Playground) { GameState::Game } else { GameState::Levels });
+                    }
                     MenuButtonAction::Playground => {
                         game_state.set(GameState::Game);
                         menu_state.set(MenuState::Disabled);
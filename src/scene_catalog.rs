@@ -0,0 +1,169 @@
+use std::collections::BTreeMap;
+
+use bevy::{asset::AssetLoadFailedEvent, gltf::{Gltf, GltfMeshExtras}, prelude::*};
+use rand::{Rng, seq::IndexedRandom};
+
+use crate::entity_pipeline::{on_level_scene_spawn, on_shape_scene_spawn, on_structure_scene_spawn};
+
+/// Loads the glb files and indexes their scenes by name, so scenes are spawned by name instead of `GltfAssetLabel` indices
+pub fn scene_catalog_plugin(
+    app: &mut App,
+) {
+    app
+        .init_resource::<SceneCatalog>()
+        .add_systems(Startup, load_scene_files)
+        .add_systems(Update, (index_scene_files, report_failed_scene_files));
+}
+
+
+/// Which glb file a scene comes from, this decides which physics observer the scene gets
+/// - Also inserted on every scene spawned through the catalog so they can be queried by kind
+#[derive(Component, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum SceneKind {
+    Map,
+    Shape,
+    Structure,
+}
+
+impl SceneKind {
+    /// Path of the glb file holding the scenes of this kind
+    pub fn file(&self) -> &'static str {
+        match self {
+            SceneKind::Map => "maps.glb",
+            SceneKind::Shape => "shapes.glb",
+            SceneKind::Structure => "structures.glb",
+        }
+    }
+}
+
+/// Every named scene found in the glb files
+/// - Filled in once a file is loaded, and again when it is reloaded
+/// - Names are the scene names given in Blender (e.g. `cube`, `target`, `cube-tower-3`)
+#[derive(Resource, Default)]
+pub struct SceneCatalog {
+    files: Vec<CatalogFile>,
+    scenes: BTreeMap<String, CatalogScene>,
+}
+
+struct CatalogFile {
+    kind: SceneKind,
+    handle: Handle<Gltf>,
+    /// Indexed, or failed to load, nothing more will come from this file until it is reloaded
+    settled: bool,
+}
+
+struct CatalogScene {
+    kind: SceneKind,
+    handle: Handle<Scene>,
+    /// At least one mesh of the scene has physics extras, scenes without any (e.g. `target`) are only used as visuals
+    physics: bool,
+}
+
+impl SceneCatalog {
+    /// True once every glb file has been indexed or failed to load
+    pub fn is_ready(&self) -> bool {
+        !self.files.is_empty() && self.files.iter().all(|file| file.settled)
+    }
+
+    /// Handle of the scene called `name`, for scenes that are spawned without physics (e.g. cursors)
+    pub fn scene(&self, name: &str) -> Option<Handle<Scene>> {
+        let scene = self.scenes.get(name);
+        if scene.is_none() {
+            warn!("No scene named '{}' in the scene catalog", name);
+        }
+        scene.map(|scene| scene.handle.clone())
+    }
+
+    /// Names of every scene of a kind that has physics, sorted alphabetically
+    pub fn names(&self, kind: SceneKind) -> impl Iterator<Item = &str> {
+        self.scenes
+            .iter()
+            .filter(move |(_, scene)| scene.kind == kind && scene.physics)
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Name of a random physics scene of a kind, `None` when there is none (e.g. its file failed to load)
+    pub fn random(&self, kind: SceneKind, rng: &mut impl Rng) -> Option<&str> {
+        self.names(kind).collect::<Vec<_>>().choose(rng).copied()
+    }
+
+    /// Spawns the scene called `name` along with `bundle` and hooks up the physics observer for its kind
+    /// - Returns `None` (and logs a warning) when there is no such scene
+    pub fn spawn<'a>(&self, commands: &'a mut Commands, name: &str, bundle: impl Bundle) -> Option<EntityCommands<'a>> {
+        let kind = self.scenes.get(name)?.kind;
+        let mut entity = commands.spawn((
+            SceneRoot(self.scene(name)?),
+            kind,
+            bundle,
+        ));
+        match kind {
+            SceneKind::Map => entity.observe(on_level_scene_spawn),
+            SceneKind::Shape => entity.observe(on_shape_scene_spawn),
+            SceneKind::Structure => entity.observe(on_structure_scene_spawn),
+        };
+        Some(entity)
+    }
+}
+
+/// Starts loading every glb file, their scenes are indexed once loaded
+fn load_scene_files(
+    asset_server: Res<AssetServer>,
+    mut catalog: ResMut<SceneCatalog>,
+) {
+    catalog.files = [SceneKind::Map, SceneKind::Shape, SceneKind::Structure]
+        .into_iter()
+        .map(|kind| CatalogFile { kind, handle: asset_server.load(kind.file()), settled: false })
+        .collect();
+}
+
+/// Indexes the named scenes of a glb file whenever it finishes loading or is reloaded
+fn index_scene_files(
+    mut events: MessageReader<AssetEvent<Gltf>>,
+    gltfs: Res<Assets<Gltf>>,
+    scenes: Res<Assets<Scene>>,
+    mut catalog: ResMut<SceneCatalog>,
+) {
+    for event in events.read() {
+        let (AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id }) = event else {
+            continue;
+        };
+        let Some(file) = catalog.files.iter_mut().find(|file| file.handle.id() == *id) else {
+            continue;
+        };
+        file.settled = true;
+        let kind = file.kind;
+        let Some(gltf) = gltfs.get(*id) else {
+            continue;
+        };
+        catalog.scenes.retain(|_, scene| scene.kind != kind);
+        for (name, handle) in &gltf.named_scenes {
+            if catalog.scenes.contains_key(name.as_ref()) {
+                warn!("Scene '{}' in {} shares its name with another scene, only the last one can be spawned", name, kind.file());
+            }
+            let physics = scenes.get(handle).is_some_and(has_physics_extras);
+            catalog.scenes.insert(name.to_string(), CatalogScene { kind, handle: handle.clone(), physics });
+        }
+        info!("Indexed {} scene(s) from {}", gltf.named_scenes.len(), kind.file());
+    }
+}
+
+/// A scene has physics when one of its meshes carries extras for the physics pipeline
+fn has_physics_extras(scene: &Scene) -> bool {
+    scene
+        .world
+        .try_query::<&GltfMeshExtras>()
+        .is_some_and(|mut query| query.iter(&scene.world).next().is_some())
+}
+
+/// Logs the glb files that failed to load and stops waiting for them, their scenes just aren't in the catalog
+fn report_failed_scene_files(
+    mut events: MessageReader<AssetLoadFailedEvent<Gltf>>,
+    mut catalog: ResMut<SceneCatalog>,
+) {
+    for event in events.read() {
+        if let Some(file) = catalog.files.iter_mut().find(|file| file.handle.id() == event.id) {
+            error!("Failed to load {}, its scenes can't be spawned: {}", file.kind.file(), event.error);
+            file.settled = true;
+        }
+    }
+}
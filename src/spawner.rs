@@ -0,0 +1,374 @@
+use std::f32::consts::TAU;
+
+use avian3d::prelude::*;
+use bevy::{color, prelude::*, scene::SceneInstanceReady};
+use bevy_egui::{EguiContexts, egui};
+use rand::Rng;
+
+use crate::{GameState, history::{EditRequest, PlaygroundHistory}, inspector::vec3_drag, interactions::SceneCursor, scene_catalog::{SceneCatalog, SceneKind}};
+
+/// Spawning shapes from a panel, where the cursor last pointed at and with an initial state
+/// - `track_spawn_target`, `draw_spawn_preview` and `spawn_panel` are added by the scenes that support it (e.g. the playground)
+/// - Shapes are spawned through the `SceneCatalog`, so they go through the regular shape pipeline and the edit history
+pub fn spawner_plugin(
+    app: &mut App,
+) {
+    app
+        .init_resource::<SpawnSettings>()
+        .init_resource::<SpawnTarget>()
+        .add_systems(OnEnter(GameState::Menu), clear_spawn_target);
+}
+
+/// Where shapes went before the spawn panel, and where they still go with `SpawnPlacement::Fixed`
+const FIXED_SPAWN_POINT: Vec3 = Vec3::new(0.0, 10.0, 0.0);
+/// How far the cursor ray looks for a surface to spawn on
+const SPAWN_REACH: f32 = 500.0;
+/// Distance between a surface and the center of a shape spawned on it, at scale 1
+const SURFACE_OFFSET: f32 = 0.5;
+
+/// How the spawn panel places and launches the shapes it spawns
+#[derive(Resource)]
+pub struct SpawnSettings {
+    pub placement: SpawnPlacement,
+    pub pattern: SpawnPattern,
+    /// Number of shapes spawned by one click, ignored by `SpawnPattern::Single`
+    pub count: u32,
+    /// Distance between the centers of neighbouring shapes
+    pub spacing: f32,
+    pub scale: f32,
+    pub velocity: SpawnVelocity,
+}
+impl Default for SpawnSettings {
+    fn default() -> Self {
+        Self { placement: SpawnPlacement::Fixed, pattern: SpawnPattern::Single, count: 5, spacing: 2.0, scale: 1.0, velocity: SpawnVelocity::default() }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpawnPlacement {
+    /// Above the center of the playground
+    Fixed,
+    /// On the cursor ray, at the scroll distance
+    Cursor,
+    /// On the first surface under the cursor, falls back to the scroll distance when there is none
+    Surface,
+}
+
+/// How several shapes are laid out around the spawn point, on the horizontal plane
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpawnPattern {
+    Single,
+    Row,
+    Grid,
+    /// Square layers stacked on each other, the base being as small as the count allows
+    Pyramid,
+    /// Random positions within `radius` of the spawn point, picked again on every click
+    Scatter { radius: f32 },
+}
+
+impl SpawnPattern {
+    pub const ALL: [SpawnPattern; 5] = [SpawnPattern::Single, SpawnPattern::Row, SpawnPattern::Grid, SpawnPattern::Pyramid, SpawnPattern::DEFAULT_SCATTER];
+    pub const DEFAULT_SCATTER: SpawnPattern = SpawnPattern::Scatter { radius: 5.0 };
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SpawnPattern::Single => "Single",
+            SpawnPattern::Row => "Row",
+            SpawnPattern::Grid => "Grid",
+            SpawnPattern::Pyramid => "Pyramid",
+            SpawnPattern::Scatter { .. } => "Scatter",
+        }
+    }
+
+    /// Offsets of every shape from the spawn point
+    pub fn offsets(&self, count: u32, spacing: f32, rng: &mut impl Rng) -> Vec<Vec3> {
+        let count = count.max(1) as usize;
+        match *self {
+            SpawnPattern::Single => vec![Vec3::ZERO],
+            SpawnPattern::Row => (0..count).map(|i| square_offset(i, 0, count, 1, spacing)).collect(),
+            SpawnPattern::Grid => {
+                let columns = (count as f32).sqrt().ceil() as usize;
+                let rows = count.div_ceil(columns);
+                (0..count)
+                    .map(|i| square_offset(i % columns, i / columns, columns, rows, spacing))
+                    .collect()
+            }
+            SpawnPattern::Pyramid => {
+                let mut base = 1;
+                while (1..=base).map(|side| side * side).sum::<usize>() < count {
+                    base += 1;
+                }
+                (1..=base)
+                    .rev()
+                    .enumerate()
+                    .flat_map(|(layer, side)| {
+                        (0..side * side).map(move |i| {
+                            square_offset(i % side, i / side, side, side, spacing) + Vec3::Y * layer as f32 * spacing
+                        })
+                    })
+                    .take(count)
+                    .collect()
+            }
+            SpawnPattern::Scatter { radius } => (0..count)
+                .map(|_| {
+                    // Square root of the distance so the points are spread evenly over the disk
+                    let distance = radius * rng.random::<f32>().sqrt();
+                    let angle = rng.random::<f32>() * TAU;
+                    Vec3::new(angle.cos(), 0.0, angle.sin()) * distance
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Offset of a cell of a `columns` by `rows` square layout centered on the spawn point
+fn square_offset(column: usize, row: usize, columns: usize, rows: usize, spacing: f32) -> Vec3 {
+    Vec3::new(
+        (column as f32 - (columns - 1) as f32 / 2.0) * spacing,
+        0.0,
+        (row as f32 - (rows - 1) as f32 / 2.0) * spacing,
+    )
+}
+
+/// Velocity given to the bodies of a spawned scene once the pipeline has created them
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq)]
+pub struct SpawnVelocity {
+    pub linear: Vec3,
+    pub angular: Vec3,
+}
+
+/// Where the cursor last pointed at in the scene, kept while it is over the spawn panel
+#[derive(Resource, Default)]
+pub struct SpawnTarget {
+    cursor: Option<Vec3>,
+    /// Point and normal of the surface under the cursor
+    surface: Option<(Vec3, Vec3)>,
+}
+
+impl SpawnSettings {
+    /// Center of the pattern, `None` when the cursor hasn't pointed at the scene yet
+    fn spawn_point(&self, target: &SpawnTarget) -> Option<Vec3> {
+        match self.placement {
+            SpawnPlacement::Fixed => Some(FIXED_SPAWN_POINT),
+            SpawnPlacement::Cursor => target.cursor,
+            SpawnPlacement::Surface => target
+                .surface
+                .map(|(point, normal)| point + normal * SURFACE_OFFSET * self.scale)
+                .or(target.cursor),
+        }
+    }
+
+    /// Transforms of the shapes spawned by one click
+    fn transforms(&self, target: &SpawnTarget, rng: &mut impl Rng) -> Vec<Transform> {
+        let Some(spawn_point) = self.spawn_point(target) else {
+            return Vec::new();
+        };
+        self.pattern
+            .offsets(self.count, self.spacing, rng)
+            .into_iter()
+            .map(|offset| Transform::from_translation(spawn_point + offset).with_scale(Vec3::splat(self.scale)))
+            .collect()
+    }
+}
+
+/// Remembers what the cursor points at while it is over the scene, so it can then be moved to the panel
+pub fn track_spawn_target(
+    cursor: SceneCursor,
+    mut target: ResMut<SpawnTarget>,
+) {
+    if cursor.over_ui() || cursor.ray().is_none() {
+        return;
+    }
+    target.cursor = cursor.point();
+    target.surface = cursor.hit(SPAWN_REACH).map(|(ray, hit)| (ray.get_point(hit.distance), hit.normal));
+}
+
+fn clear_spawn_target(
+    mut target: ResMut<SpawnTarget>,
+) {
+    *target = SpawnTarget::default();
+}
+
+/// Outlines where the next shapes will be spawned when they follow the cursor, scattered shapes only show the disk they land in
+pub fn draw_spawn_preview(
+    settings: Res<SpawnSettings>,
+    target: Res<SpawnTarget>,
+    mut gizmos: Gizmos,
+) {
+    if settings.placement == SpawnPlacement::Fixed {
+        return;
+    }
+    let Some(spawn_point) = settings.spawn_point(&target) else {
+        return;
+    };
+    let radius = SURFACE_OFFSET * settings.scale;
+    let preview_color = color::palettes::css::AQUA;
+    if settings.velocity.linear != Vec3::ZERO {
+        gizmos.arrow(spawn_point, spawn_point + settings.velocity.linear * 0.25, preview_color);
+    }
+    if let SpawnPattern::Scatter { radius: scatter_radius } = settings.pattern {
+        let disk = Isometry3d::new(spawn_point, Quat::from_rotation_arc(Vec3::Z, Vec3::Y));
+        gizmos.circle(disk, scatter_radius, preview_color);
+        gizmos.sphere(Isometry3d::from_translation(spawn_point), radius, preview_color);
+        return;
+    }
+    for offset in settings.pattern.offsets(settings.count, settings.spacing, &mut rand::rng()) {
+        gizmos.sphere(Isometry3d::from_translation(spawn_point + offset), radius, preview_color);
+    }
+}
+
+/// Shape buttons along with where, how many and how fast they are spawned
+pub fn spawn_panel(
+    mut contexts: EguiContexts,
+    catalog: Res<SceneCatalog>,
+    mut settings: ResMut<SpawnSettings>,
+    target: Res<SpawnTarget>,
+    mut history: PlaygroundHistory,
+) -> Result {
+    egui::Window::new("Spawn Shapes")
+        .resizable(true)
+        .default_open(true)
+        .show(contexts.ctx_mut()?, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Position:");
+                ui.selectable_value(&mut settings.placement, SpawnPlacement::Fixed, "Fixed");
+                ui.selectable_value(&mut settings.placement, SpawnPlacement::Cursor, "Cursor")
+                    .on_hover_text("Where the cursor last pointed at, at the scroll distance");
+                ui.selectable_value(&mut settings.placement, SpawnPlacement::Surface, "Surface")
+                    .on_hover_text("On the surface the cursor last pointed at");
+            });
+            ui.horizontal(|ui| {
+                ui.label("Pattern:");
+                for pattern in SpawnPattern::ALL {
+                    let selected = settings.pattern.label() == pattern.label();
+                    if ui.selectable_label(selected, pattern.label()).clicked() && !selected {
+                        settings.pattern = pattern;
+                    }
+                }
+            });
+            ui.horizontal(|ui| {
+                if settings.pattern != SpawnPattern::Single {
+                    ui.label("Count:");
+                    ui.add(egui::DragValue::new(&mut settings.count).speed(0.1).range(1..=200));
+                }
+                match &mut settings.pattern {
+                    SpawnPattern::Single => {}
+                    SpawnPattern::Scatter { radius } => {
+                        ui.label("Radius:");
+                        ui.add(egui::DragValue::new(radius).speed(0.1).range(0.5..=100.0));
+                    }
+                    _ => {
+                        ui.label("Spacing:");
+                        ui.add(egui::DragValue::new(&mut settings.spacing).speed(0.05).range(0.1..=50.0));
+                    }
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Scale:");
+                ui.add(egui::DragValue::new(&mut settings.scale).speed(0.05).range(0.1..=10.0));
+            });
+            ui.horizontal(|ui| {
+                ui.label("Linear Velocity:");
+                vec3_drag(ui, &mut settings.velocity.linear, 0.1);
+            });
+            ui.horizontal(|ui| {
+                ui.label("Angular Velocity:");
+                vec3_drag(ui, &mut settings.velocity.angular, 0.1);
+            });
+
+            ui.separator();
+            if settings.spawn_point(&target).is_none() {
+                ui.label("Point at the scene to pick where shapes are spawned");
+            }
+            ui.horizontal_wrapped(|ui| {
+                for name in catalog.names(SceneKind::Shape) {
+                    if ui.button(name).clicked() {
+                        let transforms = settings.transforms(&target, &mut rand::rng());
+                        if !transforms.is_empty() {
+                            history.apply(EditRequest::Spawn { kind: SceneKind::Shape, name: name.to_string(), transforms, velocity: settings.velocity, replace: false });
+                        }
+                    }
+                }
+            });
+            if ui.button("Delete Shapes").clicked() {
+                history.apply(EditRequest::DeleteKind(SceneKind::Shape));
+            }
+        });
+    Ok(())
+}
+
+/// Hands the `SpawnVelocity` of a scene root to its bodies
+pub fn launch_spawned_scene(
+    trigger: On<SceneInstanceReady>,
+    mut commands: Commands,
+) {
+    // Run as a command so it comes after the pipeline observer's commands that create the bodies
+    commands.run_system_cached_with(apply_spawn_velocity, trigger.entity);
+}
+
+fn apply_spawn_velocity(
+    In(root): In<Entity>,
+    mut commands: Commands,
+    velocities: Query<&SpawnVelocity>,
+    children: Query<&Children>,
+    bodies: Query<(), With<RigidBody>>,
+) {
+    let Ok(velocity) = velocities.get(root) else {
+        return;
+    };
+    for body in children.iter_descendants(root).filter(|entity| bodies.contains(*entity)) {
+        commands.entity(body).insert((LinearVelocity(velocity.linear), AngularVelocity(velocity.angular)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    fn offsets(pattern: SpawnPattern, count: u32) -> Vec<Vec3> {
+        pattern.offsets(count, 2.0, &mut StdRng::seed_from_u64(0))
+    }
+
+    #[test]
+    fn every_pattern_spawns_the_count() {
+        for pattern in SpawnPattern::ALL {
+            let expected = if pattern == SpawnPattern::Single { 1 } else { 7 };
+            assert_eq!(offsets(pattern, 7).len(), expected, "{}", pattern.label());
+            // A count of 0 still spawns one shape
+            assert_eq!(offsets(pattern, 0).len(), 1, "{}", pattern.label());
+        }
+    }
+
+    #[test]
+    fn row_is_centred_and_spaced() {
+        assert_eq!(offsets(SpawnPattern::Row, 3), vec![Vec3::new(-2.0, 0.0, 0.0), Vec3::ZERO, Vec3::new(2.0, 0.0, 0.0)]);
+    }
+
+    #[test]
+    fn grid_fills_rows_of_the_smallest_square() {
+        let grid = offsets(SpawnPattern::Grid, 5);
+        // 3 columns, 2 rows
+        assert_eq!(grid[0], Vec3::new(-2.0, 0.0, -1.0));
+        assert_eq!(grid[2], Vec3::new(2.0, 0.0, -1.0));
+        assert_eq!(grid[3], Vec3::new(-2.0, 0.0, 1.0));
+        assert_eq!(grid[4], Vec3::new(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn pyramid_stacks_shrinking_layers() {
+        let pyramid = offsets(SpawnPattern::Pyramid, 5);
+        // A 2x2 base with one shape on top
+        let base: Vec<_> = pyramid.iter().filter(|offset| offset.y == 0.0).collect();
+        assert_eq!(base.len(), 4);
+        assert!(base.iter().all(|offset| offset.x.abs() == 1.0 && offset.z.abs() == 1.0));
+        assert_eq!(pyramid[4], Vec3::new(0.0, 2.0, 0.0));
+    }
+
+    #[test]
+    fn scatter_stays_within_the_radius() {
+        let scatter = offsets(SpawnPattern::Scatter { radius: 3.0 }, 100);
+        assert!(scatter.iter().all(|offset| offset.y == 0.0 && offset.length() <= 3.0));
+    }
+}
@@ -0,0 +1,329 @@
+use avian3d::prelude::*;
+use bevy::{color, prelude::*};
+use bevy_egui::{EguiContexts, egui};
+
+use crate::{inspector::{Selection, vec3_drag}, interactions::{InteractionMode, InteractionModeType, SceneCursor}};
+
+/// Translate/rotate/scale handles on the selected body, plus numeric entry in an egui panel
+/// - The body is kinematic while a handle is dragged so physics doesn't fight the drag, and goes back to its own type on release
+/// - Pinning or freezing is refused during a drag, otherwise the release would overwrite it with the type from before the drag
+/// - `begin_transform_drag`, `drag_transform`, `draw_transform_gizmo` and `transform_gizmo_panel` are added by the scenes that support selection
+pub fn transform_gizmo_plugin(
+    app: &mut App,
+) {
+    app.init_resource::<TransformGizmo>();
+}
+
+/// Length of the handles as a fraction of the distance to the camera, so they keep the same size on screen
+const HANDLE_SCALE: f32 = 0.15;
+/// How close to a handle the cursor ray must pass to pick it, as a fraction of the handle length
+const PICK_TOLERANCE: f32 = 0.08;
+/// Smallest scale a drag can shrink a body to
+const MIN_SCALE: f32 = 0.05;
+
+const AXIS_COLORS: [Srgba; 3] = [color::palettes::css::RED, color::palettes::css::LIME, color::palettes::css::BLUE];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GizmoMode {
+    Translate,
+    Rotate,
+    Scale,
+}
+
+#[derive(Resource)]
+pub struct TransformGizmo {
+    pub mode: GizmoMode,
+    drag: Option<GizmoDrag>,
+    /// Rotation shown in the panel, in degrees, for the body and rotation it was last entered for
+    /// - Kept so editing one angle doesn't re-derive the others from the quaternion, which jumps around ±90° pitch
+    euler: Option<(Entity, Quat, Vec3)>,
+}
+
+impl Default for TransformGizmo {
+    fn default() -> Self {
+        Self { mode: GizmoMode::Translate, drag: None, euler: None }
+    }
+}
+
+impl TransformGizmo {
+    pub fn is_dragging(&self) -> bool {
+        self.drag.is_some()
+    }
+}
+
+/// Handle being dragged and the body's state when the drag started
+struct GizmoDrag {
+    entity: Entity,
+    axis_index: usize,
+    /// Body type before the drag, restored on release
+    rigid_body: RigidBody,
+    start_position: Vec3,
+    start_rotation: Quat,
+    start_scale: Vec3,
+    /// Translate/scale: where along the axis the drag started
+    start_offset: f32,
+    /// Rotate: direction from the center to where the drag started, in the plane of the ring
+    start_direction: Vec3,
+}
+
+/// True while a handle is being dragged, used to keep the click from changing the selection
+pub fn is_dragging_gizmo(
+    gizmo: Res<TransformGizmo>,
+) -> bool {
+    gizmo.is_dragging()
+}
+
+/// World space axis of a handle, scale handles follow the body's rotation since scale is local
+fn handle_axis(mode: GizmoMode, rotation: Quat, axis_index: usize) -> Vec3 {
+    let axis = Vec3::AXES[axis_index];
+    if mode == GizmoMode::Scale { rotation * axis } else { axis }
+}
+
+fn handle_length(ray: Ray3d, center: Vec3) -> f32 {
+    center.distance(ray.origin) * HANDLE_SCALE
+}
+
+/// Where along the axis line the point closest to the ray is, and how far the ray passes from it
+/// - `None` when the ray is parallel to the axis or the closest point is behind the camera
+fn closest_on_axis(ray: Ray3d, center: Vec3, axis: Vec3) -> Option<(f32, f32)> {
+    let direction = *ray.direction;
+    let offset = center - ray.origin;
+    let cos = axis.dot(direction);
+    let denominator = 1.0 - cos * cos;
+    if denominator < 1e-4 {
+        return None;
+    }
+    let along_ray = direction.dot(offset);
+    let along_axis = axis.dot(offset);
+    let t = (cos * along_ray - along_axis) / denominator;
+    let s = along_ray + cos * t;
+    if s < 0.0 {
+        return None;
+    }
+    let distance = (offset + axis * t - direction * s).length();
+    Some((t, distance))
+}
+
+/// Direction from the center to where the ray crosses the plane of a rotation ring, and its distance from the center
+fn ring_hit(ray: Ray3d, center: Vec3, axis: Vec3) -> Option<(Vec3, f32)> {
+    let distance = ray.intersect_plane(center, InfinitePlane3d::new(axis))?;
+    let offset = ray.get_point(distance) - center;
+    Some((offset.normalize_or_zero(), offset.length()))
+}
+
+/// Starts dragging the handle under the cursor, the body turns kinematic until `release_transform_drag`
+pub fn begin_transform_drag(
+    mut commands: Commands,
+    cursor: SceneCursor,
+    selection: Res<Selection>,
+    mut gizmo: ResMut<TransformGizmo>,
+    bodies: Query<(&Position, &Rotation, &Transform, &RigidBody)>,
+) {
+    if !cursor.just_clicked() || gizmo.drag.is_some() {
+        return;
+    }
+    let (Some(entity), Some(ray)) = (selection.0, cursor.ray()) else {
+        return;
+    };
+    let Ok((position, rotation, transform, rigid_body)) = bodies.get(entity) else {
+        return;
+    };
+    let length = handle_length(ray, position.0);
+    let tolerance = length * PICK_TOLERANCE;
+
+    // Closest handle within the tolerance
+    let mut picked: Option<(usize, f32, f32, Vec3)> = None;
+    for axis_index in 0..3 {
+        let axis = handle_axis(gizmo.mode, rotation.0, axis_index);
+        let hit = match gizmo.mode {
+            GizmoMode::Translate | GizmoMode::Scale => closest_on_axis(ray, position.0, axis)
+                .filter(|(t, _)| (0.0..=length).contains(t))
+                .map(|(t, distance)| (distance, t, Vec3::ZERO)),
+            GizmoMode::Rotate => ring_hit(ray, position.0, axis)
+                .map(|(direction, radius)| ((radius - length).abs(), 0.0, direction)),
+        };
+        if let Some((distance, offset, direction)) = hit
+            && distance < tolerance
+            && picked.is_none_or(|(_, closest, _, _)| distance < closest)
+        {
+            picked = Some((axis_index, distance, offset, direction));
+        }
+    }
+    let Some((axis_index, _, start_offset, start_direction)) = picked else {
+        return;
+    };
+
+    gizmo.drag = Some(GizmoDrag {
+        entity,
+        axis_index,
+        rigid_body: *rigid_body,
+        start_position: position.0,
+        start_rotation: rotation.0,
+        start_scale: transform.scale,
+        start_offset,
+        start_direction,
+    });
+    commands.entity(entity).insert((RigidBody::Kinematic, LinearVelocity::ZERO, AngularVelocity::ZERO));
+}
+
+/// Moves, rotates or scales the dragged body to follow the cursor along the picked handle
+pub fn drag_transform(
+    cursor: SceneCursor,
+    gizmo: Res<TransformGizmo>,
+    mut bodies: Query<(&mut Position, &mut Rotation, &mut Transform)>,
+) {
+    let (Some(drag), Some(ray)) = (&gizmo.drag, cursor.ray()) else {
+        return;
+    };
+    let Ok((mut position, mut rotation, mut transform)) = bodies.get_mut(drag.entity) else {
+        return;
+    };
+    let axis = handle_axis(gizmo.mode, drag.start_rotation, drag.axis_index);
+    match gizmo.mode {
+        GizmoMode::Translate => {
+            if let Some((t, _)) = closest_on_axis(ray, drag.start_position, axis) {
+                position.0 = drag.start_position + axis * (t - drag.start_offset);
+            }
+        }
+        GizmoMode::Rotate => {
+            if let Some((direction, _)) = ring_hit(ray, drag.start_position, axis) {
+                let angle = drag.start_direction.cross(direction).dot(axis).atan2(drag.start_direction.dot(direction));
+                rotation.0 = Quat::from_axis_angle(axis, angle) * drag.start_rotation;
+            }
+        }
+        GizmoMode::Scale => {
+            if let Some((t, _)) = closest_on_axis(ray, drag.start_position, axis)
+                && drag.start_offset > 0.0
+            {
+                let factor = t / drag.start_offset;
+                transform.scale[drag.axis_index] = (drag.start_scale[drag.axis_index] * factor).max(MIN_SCALE);
+            }
+        }
+    }
+}
+
+/// Ends the drag once the button is released or another mode is picked, the body gets its own type back
+pub fn release_transform_drag(
+    mut commands: Commands,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    interaction_mode: Res<InteractionMode>,
+    mut gizmo: ResMut<TransformGizmo>,
+    bodies: Query<(), With<RigidBody>>,
+) {
+    if gizmo.drag.is_none()
+        || (interaction_mode.0 == InteractionModeType::Click && mouse_input.pressed(MouseButton::Left))
+    {
+        return;
+    }
+    if let Some(drag) = gizmo.drag.take()
+        && bodies.contains(drag.entity)
+    {
+        commands.entity(drag.entity).insert((drag.rigid_body, LinearVelocity::ZERO, AngularVelocity::ZERO));
+    }
+}
+
+/// Draws the handles of the current mode on the selected body, the dragged one in yellow
+pub fn draw_transform_gizmo(
+    cursor: SceneCursor,
+    selection: Res<Selection>,
+    gizmo: Res<TransformGizmo>,
+    bodies: Query<(&Position, &Rotation)>,
+    mut gizmos: Gizmos,
+) {
+    let (Some(entity), Some(ray)) = (selection.0, cursor.ray()) else {
+        return;
+    };
+    let Ok((position, rotation)) = bodies.get(entity) else {
+        return;
+    };
+    let center = position.0;
+    let length = handle_length(ray, center);
+    for (axis_index, axis_color) in AXIS_COLORS.into_iter().enumerate() {
+        let axis = handle_axis(gizmo.mode, rotation.0, axis_index);
+        let dragged = gizmo.drag.as_ref().is_some_and(|drag| drag.axis_index == axis_index);
+        let color = if dragged { color::palettes::css::YELLOW } else { axis_color };
+        match gizmo.mode {
+            GizmoMode::Translate => {
+                gizmos.arrow(center, center + axis * length, color);
+            }
+            GizmoMode::Rotate => {
+                let ring = Isometry3d::new(center, Quat::from_rotation_arc(Vec3::Z, axis));
+                gizmos.circle(ring, length, color);
+            }
+            GizmoMode::Scale => {
+                let end = center + axis * length;
+                gizmos.line(center, end, color);
+                gizmos.cuboid(Transform::from_translation(end).with_rotation(rotation.0).with_scale(Vec3::splat(length * 0.1)), color);
+            }
+        }
+    }
+}
+
+/// Mode picker and numeric entry of the selected body's position, rotation and scale
+/// - Typed values teleport the body, whatever its type, without going through the solver like a drag does
+pub fn transform_gizmo_panel(
+    mut contexts: EguiContexts,
+    selection: Res<Selection>,
+    mut gizmo: ResMut<TransformGizmo>,
+    mut bodies: Query<(&mut Position, &mut Rotation, &mut Transform, &mut LinearVelocity, &mut AngularVelocity)>,
+) -> Result {
+    let Some(entity) = selection.0 else {
+        return Ok(());
+    };
+    let Ok((mut position, mut rotation, mut transform, mut linear_velocity, mut angular_velocity)) = bodies.get_mut(entity) else {
+        return Ok(());
+    };
+    // Angles entered earlier are kept while nothing else rotated the body
+    let degrees = match gizmo.euler {
+        Some((euler_entity, euler_rotation, degrees)) if euler_entity == entity && euler_rotation.angle_between(rotation.0) < 1e-4 => degrees,
+        _ => {
+            let (x, y, z) = rotation.0.to_euler(EulerRot::XYZ);
+            Vec3::new(x, y, z) * 180.0 / std::f32::consts::PI
+        }
+    };
+    egui::Window::new("Transform")
+        .resizable(true)
+        .default_open(true)
+        .show(contexts.ctx_mut()?, |ui| {
+            ui.horizontal(|ui| {
+                ui.selectable_value(&mut gizmo.mode, GizmoMode::Translate, "Translate");
+                ui.selectable_value(&mut gizmo.mode, GizmoMode::Rotate, "Rotate");
+                ui.selectable_value(&mut gizmo.mode, GizmoMode::Scale, "Scale");
+            });
+            ui.label("Drag the handles in Click mode, or enter values below to teleport the body");
+
+            // Typed values teleport the body, so it is also stopped to not carry its old motion along
+            let mut moved = false;
+            ui.horizontal(|ui| {
+                ui.label("Position:");
+                let mut value = position.0;
+                if vec3_drag(ui, &mut value, 0.1) {
+                    position.0 = value;
+                    moved = true;
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Rotation (degrees):");
+                let mut degrees = degrees;
+                if vec3_drag(ui, &mut degrees, 1.0) {
+                    let radians = degrees * std::f32::consts::PI / 180.0;
+                    rotation.0 = Quat::from_euler(EulerRot::XYZ, radians.x, radians.y, radians.z);
+                    gizmo.euler = Some((entity, rotation.0, degrees));
+                    moved = true;
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Scale:");
+                let mut value = transform.scale;
+                if vec3_drag(ui, &mut value, 0.01) {
+                    transform.scale = value.max(Vec3::splat(MIN_SCALE));
+                    moved = true;
+                }
+            });
+            if moved {
+                linear_velocity.0 = Vec3::ZERO;
+                angular_velocity.0 = Vec3::ZERO;
+            }
+        });
+    Ok(())
+}